@@ -1,11 +1,23 @@
-use crate::event::SaveModalState;
+use crate::event::{ConfirmAction, SaveModalState};
+use crate::finder::Finder;
+use crate::keymap::Keymap;
+use crate::mouse::MouseAreas;
+use crate::preview::Image;
+use crate::shuffle;
+use crate::srs::{self, Schedule};
+use crate::theme::Theme;
 use dirs::config_dir;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env::current_dir;
-use std::fs::{read_dir, File};
+use std::fs::{self, read_dir, File};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use tui::widgets::ListState;
 
 const DEFAULT_CONFIG_FILENAME: &str = "exhaust.json";
@@ -19,27 +31,91 @@ pub struct Selection {
 
 bitflags! {
     #[derive(Default)]
-    pub struct SelectionFlags: u8 {
-        const A = 0b00000001;
-        const B = 0b00000010;
-        const C = 0b00000100;
-        const D = 0b00001000;
-        const E = 0b00010000;
-        const F = 0b00100000;
-        const G = 0b01000000;
-        const H = 0b10000000;
+    pub struct SelectionFlags: u32 {
+        const A = 1 << 0;
+        const B = 1 << 1;
+        const C = 1 << 2;
+        const D = 1 << 3;
+        const E = 1 << 4;
+        const F = 1 << 5;
+        const G = 1 << 6;
+        const H = 1 << 7;
+        const I = 1 << 8;
+        const J = 1 << 9;
+        const K = 1 << 10;
+        const L = 1 << 11;
+        const M = 1 << 12;
+        const N = 1 << 13;
+        const O = 1 << 14;
+        const P = 1 << 15;
+        const Q = 1 << 16;
+        const R = 1 << 17;
+        const S = 1 << 18;
+        const T = 1 << 19;
+        const U = 1 << 20;
+        const V = 1 << 21;
+        const W = 1 << 22;
+        const X = 1 << 23;
+        const Y = 1 << 24;
+        const Z = 1 << 25;
         const NONE = 0b0;
     }
 }
 
 impl SelectionFlags {
-    pub fn is_selected(&self, index: u8) -> bool {
-        self.bits() & index != 0
+    /// Selections are labelled from `A` to `Z`, so at most 26 of them can be
+    /// displayed and selected.
+    pub const MAX_SELECTIONS: usize = 26;
+
+    /// Returns the flag of the selection at `index`, or `None` if the index
+    /// has no label.
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index >= Self::MAX_SELECTIONS {
+            return None;
+        }
+        Self::from_bits(1 << index)
+    }
+
+    /// Returns the index of `label`, case insensitive.
+    pub fn index_of_label(label: char) -> Option<usize> {
+        if !label.is_ascii_alphabetic() {
+            return None;
+        }
+        Some((label.to_ascii_lowercase() as u8 - b'a') as usize)
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        Self::from_index(index)
+            .map(|flag| self.contains(flag))
+            .unwrap_or(false)
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum ScoringPolicy {
+    /// Full points only if the selections match exactly
+    #[default]
+    Exact,
+    /// Points in proportion to the number of selections judged correctly
+    Partial,
+    /// Points for each correct selection, minus a penalty for each wrong one
+    Negative,
+}
+
 pub trait HasQuestionResult {
-    fn get_result(&self) -> QuestionResult;
+    /// Returns the points earned, or `None` if not answered yet.
+    fn get_score(&self, scoring: ScoringPolicy) -> Option<f64>;
+
+    fn max_points(&self) -> f64;
+
+    fn get_result(&self, scoring: ScoringPolicy) -> QuestionResult {
+        match self.get_score(scoring) {
+            None => QuestionResult::Pending,
+            Some(points) if points >= self.max_points() => QuestionResult::Correct,
+            Some(points) if points <= 0f64 => QuestionResult::Wrong,
+            Some(_) => QuestionResult::Partial,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -51,6 +127,17 @@ pub struct Question {
     pub user_selection: SelectionFlags,
     #[serde(default)]
     pub assets: Vec<String>,
+    /// Overrides the scoring policy of the exam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<ScoringPolicy>,
+    /// Points of the question, 1 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    /// Marked by the user to come back to later
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
@@ -59,34 +146,403 @@ impl Question {
     pub fn get_should_selects(&self) -> SelectionFlags {
         let mut result = SelectionFlags::NONE;
         self.selections.iter().enumerate().for_each(|(index, sel)| {
-            SelectionFlags::from_bits(0b1 << index).and_then(|mask| {
+            if let Some(mask) = SelectionFlags::from_index(index) {
                 if sel.should_select {
                     result |= mask;
                 }
-                Some(mask)
-            });
+            }
         });
         result
     }
 }
 
 impl HasQuestionResult for Question {
-    fn get_result(&self) -> QuestionResult {
-        match self.user_selection.bits() == 0 {
-            true => QuestionResult::Pending,
-            false => match self.get_should_selects() == self.user_selection {
-                true => QuestionResult::Correct,
-                false => QuestionResult::Wrong,
-            },
+    fn get_score(&self, scoring: ScoringPolicy) -> Option<f64> {
+        if self.user_selection.is_empty() {
+            return None;
         }
+        let should_selects = self.get_should_selects();
+        let num_selections = self.num_selections().min(SelectionFlags::MAX_SELECTIONS);
+
+        let ratio = match self.scoring.unwrap_or(scoring) {
+            ScoringPolicy::Exact => match should_selects == self.user_selection {
+                true => 1f64,
+                false => 0f64,
+            },
+            ScoringPolicy::Partial => {
+                let num_judged = (0..num_selections)
+                    .filter(|index| {
+                        should_selects.is_selected(*index)
+                            == self.user_selection.is_selected(*index)
+                    })
+                    .count();
+                num_judged as f64 / num_selections.max(1) as f64
+            }
+            ScoringPolicy::Negative => {
+                // Penalties are set so that selecting every option scores 0
+                let num_should_selects = should_selects.bits().count_ones();
+                let num_wrong_options = num_selections as u32 - num_should_selects;
+                let num_hits = (self.user_selection & should_selects).bits().count_ones();
+                let num_misses = (self.user_selection - should_selects).bits().count_ones();
+                num_hits as f64 / num_should_selects.max(1) as f64
+                    - num_misses as f64 / num_wrong_options.max(1) as f64
+            }
+        };
+        Some(ratio * self.max_points())
+    }
+
+    fn max_points(&self) -> f64 {
+        self.points.unwrap_or(1f64)
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum CardGrade {
+    #[default]
+    Pending,
+    Known,
+    Unknown,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Card {
     pub question: String,
     pub answer: String,
+    #[serde(default)]
+    pub user_grade: CardGrade,
+    #[serde(default)]
+    pub assets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    /// Marked by the user to come back to later
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl HasQuestionResult for Card {
+    fn get_score(&self, _scoring: ScoringPolicy) -> Option<f64> {
+        match self.user_grade {
+            CardGrade::Pending => None,
+            CardGrade::Known => Some(self.max_points()),
+            CardGrade::Unknown => Some(0f64),
+        }
+    }
+
+    fn max_points(&self) -> f64 {
+        1f64
+    }
+}
+
+/// A fill-in-the-blank question answered with typed text.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TextQuestion {
+    pub question: String,
+    /// Answers accepted as correct
+    pub accepted_answers: Vec<String>,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Whether the accepted answers are regular expressions
+    #[serde(default)]
+    pub regex: bool,
+    pub answer: Option<String>,
+    #[serde(default)]
+    pub user_answer: String,
+    #[serde(default)]
     pub assets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+    /// The accepted answers compiled by `compile_patterns`, if regular
+    /// expressions
+    #[serde(skip)]
+    patterns: Vec<Regex>,
+}
+
+impl TextQuestion {
+    /// Compiles the accepted answers once, so that scoring does not.
+    pub fn compile_patterns(&mut self) -> Result<(), regex::Error> {
+        if !self.regex {
+            return Ok(());
+        }
+        // The whole answer must match
+        self.patterns = self
+            .accepted_answers
+            .iter()
+            .map(|accepted| {
+                RegexBuilder::new(&format!("^(?:{})$", accepted))
+                    .case_insensitive(!self.case_sensitive)
+                    .build()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    /// Trims the answer and collapses whitespace. Case is ignored unless the
+    /// question is case sensitive.
+    fn normalize(&self, text: &str) -> String {
+        let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+        match self.case_sensitive {
+            true => text,
+            false => text.to_lowercase(),
+        }
+    }
+
+    pub fn is_accepted(&self, user_answer: &str) -> bool {
+        let user_answer = self.normalize(user_answer);
+        match self.regex {
+            true => self.patterns.iter().any(|re| re.is_match(&user_answer)),
+            false => self
+                .accepted_answers
+                .iter()
+                .any(|accepted| self.normalize(accepted) == user_answer),
+        }
+    }
+}
+
+impl HasQuestionResult for TextQuestion {
+    fn get_score(&self, _scoring: ScoringPolicy) -> Option<f64> {
+        if self.user_answer.trim().is_empty() {
+            return None;
+        }
+        match self.is_accepted(&self.user_answer) {
+            true => Some(self.max_points()),
+            false => Some(0f64),
+        }
+    }
+
+    fn max_points(&self) -> f64 {
+        self.points.unwrap_or(1f64)
+    }
+}
+
+/// A question answered with a number, accepted within a tolerance.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NumericQuestion {
+    pub question: String,
+    pub value: f64,
+    /// Maximum absolute difference from `value` accepted as correct
+    #[serde(default)]
+    pub tolerance: f64,
+    pub answer: Option<String>,
+    /// The answer as typed, so that it can be edited
+    #[serde(default)]
+    pub user_answer: String,
+    #[serde(default)]
+    pub assets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl NumericQuestion {
+    pub fn is_accepted(&self, user_answer: &str) -> bool {
+        user_answer
+            .trim()
+            .parse::<f64>()
+            .map(|number| (number - self.value).abs() <= self.tolerance.abs())
+            .unwrap_or(false)
+    }
+}
+
+impl HasQuestionResult for NumericQuestion {
+    fn get_score(&self, _scoring: ScoringPolicy) -> Option<f64> {
+        if self.user_answer.trim().is_empty() {
+            return None;
+        }
+        match self.is_accepted(&self.user_answer) {
+            true => Some(self.max_points()),
+            false => Some(0f64),
+        }
+    }
+
+    fn max_points(&self) -> f64 {
+        self.points.unwrap_or(1f64)
+    }
+}
+
+/// A statement judged as true or false.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TrueFalseQuestion {
+    pub question: String,
+    /// Whether the statement is true
+    pub value: bool,
+    pub answer: Option<String>,
+    #[serde(default)]
+    pub user_answer: Option<bool>,
+    #[serde(default)]
+    pub assets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl HasQuestionResult for TrueFalseQuestion {
+    fn get_score(&self, _scoring: ScoringPolicy) -> Option<f64> {
+        match self.user_answer? == self.value {
+            true => Some(self.max_points()),
+            false => Some(0f64),
+        }
+    }
+
+    fn max_points(&self) -> f64 {
+        self.points.unwrap_or(1f64)
+    }
+}
+
+/// Steps to be arranged in the right order.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderingQuestion {
+    pub question: String,
+    /// Steps in the right order
+    pub steps: Vec<String>,
+    pub answer: Option<String>,
+    /// Indices of the steps in the order arranged by the user. Empty if not
+    /// answered yet.
+    #[serde(default)]
+    pub user_order: Vec<usize>,
+    #[serde(default)]
+    pub assets: Vec<String>,
+    /// Overrides the scoring policy of the exam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<ScoringPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl OrderingQuestion {
+    /// Returns whether `user_order` arranges every step exactly once.
+    pub fn is_answered(&self) -> bool {
+        let mut sorted = self.user_order.clone();
+        sorted.sort();
+        sorted.len() == self.steps.len() && sorted.iter().enumerate().all(|(i, step)| i == *step)
+    }
+}
+
+impl HasQuestionResult for OrderingQuestion {
+    fn get_score(&self, scoring: ScoringPolicy) -> Option<f64> {
+        if !self.is_answered() {
+            return None;
+        }
+        // Pairs of steps are concordant if arranged in the right order
+        let num_steps = self.user_order.len();
+        let num_pairs = num_steps * num_steps.saturating_sub(1) / 2;
+        let num_concordant = (0..num_steps)
+            .flat_map(|i| (i + 1..num_steps).map(move |j| (i, j)))
+            .filter(|(i, j)| self.user_order[*i] < self.user_order[*j])
+            .count();
+        let num_discordant = num_pairs - num_concordant;
+
+        let ratio = match self.scoring.unwrap_or(scoring) {
+            ScoringPolicy::Exact => match num_discordant == 0 {
+                true => 1f64,
+                false => 0f64,
+            },
+            ScoringPolicy::Partial => num_concordant as f64 / num_pairs.max(1) as f64,
+            // Kendall's tau, so that the reversed order scores -1
+            ScoringPolicy::Negative => {
+                (num_concordant as f64 - num_discordant as f64) / num_pairs.max(1) as f64
+            }
+        };
+        Some(ratio * self.max_points())
+    }
+
+    fn max_points(&self) -> f64 {
+        self.points.unwrap_or(1f64)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Pair {
+    pub left: String,
+    pub right: String,
+}
+
+/// Entries of the left column to be paired with entries of the right column.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MatchingQuestion {
+    pub question: String,
+    pub pairs: Vec<Pair>,
+    pub answer: Option<String>,
+    /// Index of the right entry paired with each left entry by the user
+    #[serde(default)]
+    pub user_matches: Vec<Option<usize>>,
+    #[serde(default)]
+    pub assets: Vec<String>,
+    /// Overrides the scoring policy of the exam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<ScoringPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub flagged: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl MatchingQuestion {
+    pub fn num_pairs(&self) -> usize {
+        self.pairs.len().min(SelectionFlags::MAX_SELECTIONS)
+    }
+
+    /// Returns the index of the right entry paired with the `left`-th entry.
+    pub fn user_match(&self, left: usize) -> Option<usize> {
+        self.user_matches.get(left).copied().flatten()
+    }
+}
+
+impl HasQuestionResult for MatchingQuestion {
+    fn get_score(&self, scoring: ScoringPolicy) -> Option<f64> {
+        let num_pairs = self.num_pairs();
+        let num_matched = (0..num_pairs)
+            .filter(|left| self.user_match(*left).is_some())
+            .count();
+        if num_matched == 0 {
+            return None;
+        }
+        let num_correct = (0..num_pairs)
+            .filter(|left| self.user_match(*left) == Some(*left))
+            .count();
+        let num_wrong = num_matched - num_correct;
+
+        let ratio = match self.scoring.unwrap_or(scoring) {
+            ScoringPolicy::Exact => match num_correct == num_pairs {
+                true => 1f64,
+                false => 0f64,
+            },
+            ScoringPolicy::Partial => num_correct as f64 / num_pairs as f64,
+            ScoringPolicy::Negative => (num_correct as f64 - num_wrong as f64) / num_pairs as f64,
+        };
+        Some(ratio * self.max_points())
+    }
+
+    fn max_points(&self) -> f64 {
+        self.points.unwrap_or(1f64)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -94,11 +550,60 @@ pub struct Card {
 pub enum Item {
     Question(Question),
     Card(Card),
+    Text(TextQuestion),
+    Numeric(NumericQuestion),
+    TrueFalse(TrueFalseQuestion),
+    Ordering(OrderingQuestion),
+    Matching(MatchingQuestion),
+}
+
+impl HasQuestionResult for Item {
+    fn get_score(&self, scoring: ScoringPolicy) -> Option<f64> {
+        match self {
+            Item::Question(question) => question.get_score(scoring),
+            Item::Card(card) => card.get_score(scoring),
+            Item::Text(question) => question.get_score(scoring),
+            Item::Numeric(question) => question.get_score(scoring),
+            Item::TrueFalse(question) => question.get_score(scoring),
+            Item::Ordering(question) => question.get_score(scoring),
+            Item::Matching(question) => question.get_score(scoring),
+        }
+    }
+
+    fn max_points(&self) -> f64 {
+        match self {
+            Item::Question(question) => question.max_points(),
+            Item::Card(card) => card.max_points(),
+            Item::Text(question) => question.max_points(),
+            Item::Numeric(question) => question.max_points(),
+            Item::TrueFalse(question) => question.max_points(),
+            Item::Ordering(question) => question.max_points(),
+            Item::Matching(question) => question.max_points(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Exam {
+    /// Shown and searched in the exam finder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     pub questions: Vec<Item>,
+    /// Scoring policy of questions without their own
+    #[serde(default, skip_serializing_if = "is_exact")]
+    pub scoring: ScoringPolicy,
+    /// Time limit in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<u64>,
+    /// Time spent in seconds, kept to resume a timed exam
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub elapsed_time: u64,
+    /// Set once a timed exam is submitted, after which answers are locked
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub submitted: bool,
+    /// Seed of the order of questions and selections, if shuffled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shuffle_seed: Option<u64>,
     #[serde(skip)]
     pub display: DoExamDisplay,
     #[serde(flatten)]
@@ -113,6 +618,7 @@ pub struct Exam {
 pub enum QuestionResult {
     Pending,
     Correct,
+    Partial,
     Wrong,
     Done,
 }
@@ -122,6 +628,25 @@ pub struct DoExamDisplay {
     pub question_index: usize,
     pub question_scroll_pos: u16,
     pub display_answer: bool,
+    pub card_flipped: bool,
+    /// Indices of the items under review. Navigation is limited to these
+    /// items if set.
+    pub review_items: Option<Vec<usize>>,
+    /// Whether the items under review are the ones due for spaced repetition
+    pub studying: bool,
+    /// Indices of the items in the order they are displayed, if shuffled
+    pub order: Option<Vec<usize>>,
+    /// The answer being typed, if editing the answer of a text item
+    pub answer_input: Option<String>,
+    /// Position of the entry selected in an ordering or matching item
+    pub entry_cursor: usize,
+    /// The search being typed, if any
+    pub search_input: Option<String>,
+    /// The last submitted search, listed until closed
+    pub search: Option<ExamSearch>,
+    /// Time limit in seconds given on the command line. It replaces the one
+    /// of the exam for this session and is never saved.
+    pub time_limit: Option<u64>,
 }
 
 impl Default for DoExamDisplay {
@@ -130,14 +655,45 @@ impl Default for DoExamDisplay {
             question_index: 0,
             question_scroll_pos: 0,
             display_answer: false,
+            card_flipped: false,
+            review_items: None,
+            studying: false,
+            order: None,
+            answer_input: None,
+            entry_cursor: 0,
+            search_input: None,
+            search: None,
+            time_limit: None,
         }
     }
 }
 
+impl DoExamDisplay {
+    /// Returns the search to highlight in the texts, empty if none.
+    pub fn search_query(&self) -> &str {
+        match &self.search {
+            Some(search) => &search.query,
+            None => "",
+        }
+    }
+}
+
+/// The items matching a search across the exam.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExamSearch {
+    /// The submitted search, in lowercase
+    pub query: String,
+    /// Indices of the matching items, in the order they are displayed
+    pub results: Vec<usize>,
+    /// Position of the current result
+    pub current: usize,
+}
+
 #[derive(Debug)]
 pub enum AppRoute {
     Home,
     DoExam,
+    Results,
 }
 
 impl Default for AppRoute {
@@ -163,6 +719,10 @@ pub struct Home {
     pub current_path: PathBuf,
     pub list_state: ListState,
     pub open_mode: OpenMode,
+    /// Whether exams without a recorded seed are shuffled when opened
+    pub shuffle: bool,
+    /// The fuzzy finder of exams under `current_path`, if open
+    pub finder: Option<Finder>,
 }
 
 impl Default for Home {
@@ -172,6 +732,8 @@ impl Default for Home {
             current_path: current_dir().expect("Unable to get current directory"),
             list_state: ListState::default(),
             open_mode: OpenMode::default(),
+            shuffle: false,
+            finder: None,
         }
     }
 }
@@ -184,6 +746,18 @@ impl Home {
             .map(|path| path.to_path_buf())
     }
 
+    /// Browses to the directory containing `path` and highlights it.
+    pub fn select_path(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            self.current_path = parent.to_path_buf();
+        }
+        let index = self
+            .get_paths()
+            .ok()
+            .and_then(|paths| paths.iter().position(|p| p == path));
+        self.list_state.select(index);
+    }
+
     pub fn get_paths(&self) -> Result<Vec<PathBuf>, std::io::Error> {
         read_dir(&self.current_path).map(|result| {
             let mut paths: Vec<PathBuf> = result
@@ -210,14 +784,122 @@ impl Home {
     }
 }
 
+#[derive(Default)]
+pub struct Results {
+    pub list_state: ListState,
+    /// Feedback of the last export
+    pub status: Option<String>,
+}
+
 pub enum AssetsModalState {
     Hidden,
-    Show(ListState),
+    /// The list of assets, and the preview of the selected image asset
+    Show(ListState, Option<AssetPreview>),
+    /// A text asset opened from the list
+    View(ListState, TextView),
+}
+
+/// The preview of an image asset, decoded in the background.
+pub enum AssetPreview {
+    Loading(PathBuf),
+    Loaded(Result<Image, String>),
+}
+
+/// A plain-text asset shown in the assets modal.
+pub struct TextView {
+    pub title: String,
+    pub lines: Vec<String>,
+    /// Index of the first visible line
+    pub scroll: usize,
+    /// Number of visible lines, updated when drawn
+    pub height: usize,
+    /// The search being typed, if any
+    pub search_input: Option<String>,
+    /// The last submitted search, in lowercase
+    pub search: String,
+    /// Line of the current match
+    pub current_match: Option<usize>,
+}
+
+impl TextView {
+    /// Files larger than this are left to the launcher
+    const MAX_SIZE: u64 = 1 << 20;
+
+    /// Reads the file at `path`, failing if it does not look like text.
+    pub fn load(path: &Path, title: &str) -> Result<TextView, io::Error> {
+        let invalid_data = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        if path.metadata()?.len() > Self::MAX_SIZE {
+            return Err(invalid_data("File too large"));
+        }
+        let text = fs::read_to_string(path)?;
+        if text.contains('\0') {
+            return Err(invalid_data("Binary file"));
+        }
+
+        Ok(TextView {
+            title: title.to_owned(),
+            lines: text
+                .lines()
+                .map(|line| line.replace('\t', "    "))
+                .collect(),
+            scroll: 0,
+            height: 1,
+            search_input: None,
+            search: String::new(),
+            current_match: None,
+        })
+    }
+
+    /// Scrolls by `delta` lines, without scrolling past the last line.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max_scroll = self.lines.len().saturating_sub(self.height.max(1));
+        let scroll = self.scroll as isize + delta;
+        self.scroll = (scroll.max(0) as usize).min(max_scroll);
+    }
+
+    /// Moves to the next line matching the search after the current match,
+    /// or the previous one if not `forward`. Searches wrap around the text.
+    pub fn find(&mut self, forward: bool) -> Option<usize> {
+        if self.search.is_empty() || self.lines.is_empty() {
+            return None;
+        }
+        let len = self.lines.len();
+        let start = match (self.current_match, forward) {
+            (Some(current), true) => current + 1,
+            (Some(current), false) => current + len - 1,
+            (None, _) => self.scroll,
+        };
+        let index = (0..len)
+            .map(|offset| match forward {
+                true => (start + offset) % len,
+                false => (start + len - offset) % len,
+            })
+            .find(|index| self.lines[*index].to_lowercase().contains(&self.search))?;
+
+        self.current_match = Some(index);
+        // Show the match near the top, with a line of context
+        self.scroll = 0;
+        self.scroll_by(index as isize - 1);
+        Some(index)
+    }
+}
+
+pub enum ErrorModalState {
+    Hidden,
+    Show(String),
+}
+
+/// A question asked before an action that discards answers
+pub enum ConfirmModalState {
+    Hidden,
+    Show(String, ConfirmAction),
 }
 
 pub struct Modal {
     pub save_modal_state: SaveModalState,
     pub assets_modal_state: AssetsModalState,
+    pub error_modal_state: ErrorModalState,
+    pub confirm_modal_state: ConfirmModalState,
 }
 
 impl Default for Modal {
@@ -225,6 +907,8 @@ impl Default for Modal {
         Modal {
             save_modal_state: SaveModalState::Hidden,
             assets_modal_state: AssetsModalState::Hidden,
+            error_modal_state: ErrorModalState::Hidden,
+            confirm_modal_state: ConfirmModalState::Hidden,
         }
     }
 }
@@ -239,6 +923,22 @@ pub struct Config {
     pub pretty_printing: bool,
     #[serde(default = "Config::_default_launcher")]
     pub launcher: String,
+    #[serde(default = "Config::_default_backup_on_save")]
+    pub backup_on_save: bool,
+    /// Preview image assets in the terminal
+    #[serde(default = "Config::_default_image_preview")]
+    pub image_preview: bool,
+    /// Capture the mouse for clicking and scrolling. Disable to select
+    /// text with the mouse as usual.
+    #[serde(default = "Config::_default_mouse")]
+    pub mouse: bool,
+    /// How many directories deep the exam finder looks
+    #[serde(default = "Config::_default_finder_depth")]
+    pub finder_depth: usize,
+    #[serde(default = "Config::_default_keymap")]
+    pub keymap: Keymap,
+    #[serde(default = "Config::_default_theme")]
+    pub theme: Theme,
 }
 
 impl Config {
@@ -251,6 +951,24 @@ impl Config {
     const fn _default_pretty_printing() -> bool {
         false
     }
+    const fn _default_backup_on_save() -> bool {
+        false
+    }
+    const fn _default_image_preview() -> bool {
+        true
+    }
+    const fn _default_mouse() -> bool {
+        true
+    }
+    const fn _default_finder_depth() -> usize {
+        5
+    }
+    fn _default_keymap() -> Keymap {
+        Keymap::default()
+    }
+    fn _default_theme() -> Theme {
+        Theme::default()
+    }
     fn _default_launcher() -> String {
         #[cfg(target_os = "macos")]
         {
@@ -274,8 +992,61 @@ impl Default for Config {
             show_usage: Self::_default_show_usage(),
             pretty_printing: Self::_default_pretty_printing(),
             launcher: Self::_default_launcher(),
+            backup_on_save: Self::_default_backup_on_save(),
+            image_preview: Self::_default_image_preview(),
+            mouse: Self::_default_mouse(),
+            finder_depth: Self::_default_finder_depth(),
+            keymap: Self::_default_keymap(),
+            theme: Self::_default_theme(),
+        }
+    }
+}
+
+/// Orders the saves running in background threads.
+///
+/// Every save takes a ticket from `issued`. A save only writes if no save with
+/// a later ticket has been written yet, so an outdated snapshot never
+/// overwrites a newer one.
+#[derive(Clone, Default)]
+pub struct SaveQueue {
+    issued: Arc<AtomicUsize>,
+    pending: Arc<AtomicUsize>,
+    written: Arc<Mutex<usize>>,
+}
+
+impl SaveQueue {
+    pub fn issue(&self) -> usize {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.issued.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Blocks until all issued saves have finished.
+    pub fn wait(&self) {
+        while self.pending.load(Ordering::SeqCst) > 0 {
+            thread::sleep(Duration::from_millis(10));
         }
     }
+
+    pub fn is_latest(&self, ticket: usize) -> bool {
+        self.issued.load(Ordering::SeqCst) == ticket
+    }
+
+    /// Runs `write` exclusively, unless a newer save has been written.
+    pub fn write<F>(&self, ticket: usize, write: F) -> Option<io::Result<()>>
+    where
+        F: FnOnce() -> io::Result<()>,
+    {
+        let mut written = self.written.lock().unwrap_or_else(|err| err.into_inner());
+        let result = match *written > ticket {
+            true => None,
+            false => Some(write()),
+        };
+        if let Some(Ok(())) = result {
+            *written = ticket;
+        }
+        self.pending.fetch_sub(1, Ordering::SeqCst);
+        result
+    }
 }
 
 #[derive(Default)]
@@ -283,18 +1054,30 @@ pub struct App {
     pub route: AppRoute,
     pub exam: Option<Exam>,
     pub home: Home,
+    pub results: Results,
     pub modal: Modal,
     pub config: Config,
+    pub save_queue: SaveQueue,
+    pub mouse_areas: MouseAreas,
 }
 
 impl App {
-    pub fn load_config(&mut self) -> Result<(), io::Error> {
+    /// Loads the config from `config_path`, or from `exhaust.json` in the
+    /// config directory if not given. A default config is written if the file
+    /// does not exist.
+    pub fn load_config(&mut self, config_path: Option<&Path>) -> Result<(), io::Error> {
         // Get config path
-        let mut config_path = config_dir().ok_or(io::Error::new(
-            io::ErrorKind::NotFound,
-            "Config directory not found",
-        ))?;
-        config_path.push(DEFAULT_CONFIG_FILENAME);
+        let config_path = match config_path {
+            Some(config_path) => config_path.to_path_buf(),
+            None => {
+                let mut config_path = config_dir().ok_or(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Config directory not found",
+                ))?;
+                config_path.push(DEFAULT_CONFIG_FILENAME);
+                config_path
+            }
+        };
         match config_path.exists() {
             true => {
                 let file = File::open(&config_path)?;
@@ -317,9 +1100,61 @@ impl App {
             }
         }
     }
+
+    /// Resolves the path of an asset relative to the exam file, or to the
+    /// browsed directory if the exam has not been saved yet.
+    pub fn asset_path(&self, asset: &str) -> PathBuf {
+        self.home
+            .exam_src
+            .as_ref()
+            .and_then(|exam_src| exam_src.parent())
+            .unwrap_or(&self.home.current_path)
+            .join(asset)
+    }
 }
 
 impl Exam {
+    /// Compiles the patterns of text items, returning the index of the first
+    /// item with an invalid one.
+    pub fn compile_patterns(&mut self) -> Result<(), (usize, regex::Error)> {
+        self.questions
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, item)| match item {
+                Item::Text(question) => question.compile_patterns().map_err(|err| (index, err)),
+                _ => Ok(()),
+            })
+    }
+
+    /// Returns the time limit in seconds, if the exam is timed.
+    pub fn time_limit(&self) -> Option<u64> {
+        self.display.time_limit.or(self.time_limit)
+    }
+
+    /// Returns the remaining seconds of a timed exam.
+    pub fn time_left(&self) -> Option<u64> {
+        self.time_limit()
+            .map(|time_limit| time_limit.saturating_sub(self.elapsed_time))
+    }
+
+    pub fn is_time_up(&self) -> bool {
+        self.time_left() == Some(0)
+    }
+
+    /// A timed exam is submitted once the time is up or the results are
+    /// first shown. Hiding the results again does not unlock the answers.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted || self.is_time_up()
+    }
+
+    /// Submits a timed exam, locking its answers.
+    pub fn submit(&mut self) {
+        if self.time_limit().is_some() && !self.submitted {
+            self.submitted = true;
+            self.unsaved_changes = true;
+        }
+    }
+
     pub fn question_at(&self, index: usize) -> Option<&Item> {
         self.questions.get(index)
     }
@@ -331,15 +1166,303 @@ impl Exam {
     pub fn num_questions(&self) -> usize {
         self.questions.len()
     }
+
+    /// Shuffles the display order with `shuffle_seed`, if set.
+    pub fn apply_shuffle(&mut self) {
+        self.display.order = self
+            .shuffle_seed
+            .map(|seed| shuffle::permutation(self.num_questions(), seed));
+        if let Some(first_index) = self.display.order.as_ref().and_then(|order| order.first()) {
+            self.display.question_index = *first_index;
+        }
+    }
+
+    /// Returns the indices of all items in the order they are displayed.
+    pub fn order(&self) -> Vec<usize> {
+        match &self.display.order {
+            Some(order) => order.clone(),
+            None => (0..self.num_questions()).collect(),
+        }
+    }
+
+    /// Returns the indices of the items that can be navigated to, in the
+    /// order they are displayed.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        match &self.display.review_items {
+            Some(review_items) => review_items.clone(),
+            None => self.order(),
+        }
+    }
+
+    /// Returns the indices of the visible items with a text containing
+    /// `search`, which must be in lowercase.
+    pub fn search(&self, search: &str) -> Vec<usize> {
+        self.visible_indices()
+            .into_iter()
+            .filter(|index| {
+                self.question_at(*index)
+                    .map(|item| {
+                        item.searchable_texts()
+                            .iter()
+                            .any(|text| text.to_lowercase().contains(search))
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Returns the 1-based number the item at `index` is displayed with.
+    pub fn display_number(&self, index: usize) -> usize {
+        match &self.display.order {
+            Some(order) => order.iter().position(|i| *i == index).unwrap_or(index) + 1,
+            None => index + 1,
+        }
+    }
+
+    /// Returns the index of the item displayed with the 1-based `number`.
+    pub fn index_at_number(&self, number: usize) -> Option<usize> {
+        match &self.display.order {
+            Some(order) => order.get(number.checked_sub(1)?).copied(),
+            None => number
+                .checked_sub(1)
+                .filter(|index| *index < self.num_questions()),
+        }
+    }
+
+    /// Returns the indices of the steps of the ordering item at `index` in the
+    /// order they are displayed. Steps are scrambled until arranged by the user.
+    pub fn step_order(&self, index: usize) -> Vec<usize> {
+        let question = match self.question_at(index) {
+            Some(Item::Ordering(question)) => question,
+            _ => return vec![],
+        };
+        if question.is_answered() {
+            return question.user_order.clone();
+        }
+
+        let num_steps = question.steps.len();
+        let seed = shuffle::derive_seed(self.shuffle_seed.unwrap_or(0), index);
+        let mut order = shuffle::permutation(num_steps, seed);
+        // The steps should not start in the right order
+        if order.len() > 1 && order.iter().enumerate().all(|(i, step)| i == *step) {
+            order.rotate_left(1);
+        }
+        order
+    }
+
+    /// Returns the indices of the right entries of the matching item at
+    /// `index` in the order they are displayed.
+    pub fn match_order(&self, index: usize) -> Vec<usize> {
+        let num_pairs = match self.question_at(index) {
+            Some(Item::Matching(question)) => question.num_pairs(),
+            _ => return vec![],
+        };
+        // Right entries are always scrambled, or they would line up with
+        // the left entries they pair with
+        let seed = shuffle::derive_seed(self.shuffle_seed.unwrap_or(0), index);
+        let mut order = shuffle::permutation(num_pairs, seed);
+        if order.len() > 1 && order.iter().enumerate().all(|(i, right)| i == *right) {
+            order.rotate_left(1);
+        }
+        order
+    }
+
+    /// Returns the indices of the selections of the item at `index` in the
+    /// order they are displayed.
+    pub fn selection_order(&self, index: usize) -> Vec<usize> {
+        let num_selections = match self.question_at(index) {
+            Some(Item::Question(question)) => question
+                .num_selections()
+                .min(SelectionFlags::MAX_SELECTIONS),
+            _ => 0,
+        };
+        match self.shuffle_seed {
+            Some(seed) => shuffle::permutation(num_selections, shuffle::derive_seed(seed, index)),
+            None => (0..num_selections).collect(),
+        }
+    }
 }
 
 impl Item {
+    pub fn get_text(&self) -> &str {
+        match self {
+            Item::Question(question) => &question.question,
+            Item::Card(card) => &card.question,
+            Item::Text(question) => &question.question,
+            Item::Numeric(question) => &question.question,
+            Item::TrueFalse(question) => &question.question,
+            Item::Ordering(question) => &question.question,
+            Item::Matching(question) => &question.question,
+        }
+    }
+
+    /// Returns the texts matched by a search: the question, the answer, and
+    /// the selections, steps or pairs of the item.
+    pub fn searchable_texts(&self) -> Vec<&str> {
+        let mut texts = vec![self.get_text()];
+        let answer = match self {
+            Item::Question(question) => question.answer.as_deref(),
+            Item::Card(card) => Some(card.answer.as_str()),
+            Item::Text(question) => question.answer.as_deref(),
+            Item::Numeric(question) => question.answer.as_deref(),
+            Item::TrueFalse(question) => question.answer.as_deref(),
+            Item::Ordering(question) => question.answer.as_deref(),
+            Item::Matching(question) => question.answer.as_deref(),
+        };
+        texts.extend(answer);
+        match self {
+            Item::Question(question) => {
+                texts.extend(question.selections.iter().map(|sel| sel.text.as_str()))
+            }
+            Item::Ordering(question) => texts.extend(question.steps.iter().map(String::as_str)),
+            Item::Matching(question) => question.pairs.iter().for_each(|pair| {
+                texts.push(&pair.left);
+                texts.push(&pair.right);
+            }),
+            _ => {}
+        }
+        texts
+    }
+
     pub fn get_assets(&self) -> &Vec<String> {
         match self {
             Item::Question(question) => &question.assets,
             Item::Card(card) => &card.assets,
+            Item::Text(question) => &question.assets,
+            Item::Numeric(question) => &question.assets,
+            Item::TrueFalse(question) => &question.assets,
+            Item::Ordering(question) => &question.assets,
+            Item::Matching(question) => &question.assets,
+        }
+    }
+
+    pub fn get_extra(&self) -> &HashMap<String, serde_json::Value> {
+        match self {
+            Item::Question(question) => &question.extra,
+            Item::Card(card) => &card.extra,
+            Item::Text(question) => &question.extra,
+            Item::Numeric(question) => &question.extra,
+            Item::TrueFalse(question) => &question.extra,
+            Item::Ordering(question) => &question.extra,
+            Item::Matching(question) => &question.extra,
+        }
+    }
+
+    /// Returns whether the item is due for review on `day`. Items never
+    /// reviewed are always due.
+    pub fn is_due(&self, day: u64) -> bool {
+        self.schedule()
+            .map(|schedule| schedule.is_due(day))
+            .unwrap_or(true)
+    }
+
+    /// Returns the spaced repetition state, if the item was ever reviewed.
+    pub fn schedule(&self) -> Option<&Schedule> {
+        match self {
+            Item::Question(question) => question.schedule.as_ref(),
+            Item::Card(card) => card.schedule.as_ref(),
+            Item::Text(question) => question.schedule.as_ref(),
+            Item::Numeric(question) => question.schedule.as_ref(),
+            Item::TrueFalse(question) => question.schedule.as_ref(),
+            Item::Ordering(question) => question.schedule.as_ref(),
+            Item::Matching(question) => question.schedule.as_ref(),
+        }
+    }
+
+    /// Records the current answer of the item as a review on `day`. Only the
+    /// first answer of a day is recorded.
+    pub fn record_review(&mut self, scoring: ScoringPolicy, day: u64) {
+        let quality = match self {
+            Item::Card(card) => srs::quality_of_grade(&card.user_grade),
+            _ => srs::quality_of_result(&self.get_result(scoring)),
+        };
+        let quality = match quality {
+            Some(quality) => quality,
+            None => return,
+        };
+        let schedule = match self {
+            Item::Question(question) => &mut question.schedule,
+            Item::Card(card) => &mut card.schedule,
+            Item::Text(question) => &mut question.schedule,
+            Item::Numeric(question) => &mut question.schedule,
+            Item::TrueFalse(question) => &mut question.schedule,
+            Item::Ordering(question) => &mut question.schedule,
+            Item::Matching(question) => &mut question.schedule,
+        }
+        .get_or_insert_with(Schedule::default);
+
+        if schedule.last_review_day() != Some(day) {
+            schedule.review(quality, day);
         }
     }
+
+    pub fn is_flagged(&self) -> bool {
+        match self {
+            Item::Question(question) => question.flagged,
+            Item::Card(card) => card.flagged,
+            Item::Text(question) => question.flagged,
+            Item::Numeric(question) => question.flagged,
+            Item::TrueFalse(question) => question.flagged,
+            Item::Ordering(question) => question.flagged,
+            Item::Matching(question) => question.flagged,
+        }
+    }
+
+    pub fn toggle_flag(&mut self) {
+        match self {
+            Item::Question(question) => question.flagged = !question.flagged,
+            Item::Card(card) => card.flagged = !card.flagged,
+            Item::Text(question) => question.flagged = !question.flagged,
+            Item::Numeric(question) => question.flagged = !question.flagged,
+            Item::TrueFalse(question) => question.flagged = !question.flagged,
+            Item::Ordering(question) => question.flagged = !question.flagged,
+            Item::Matching(question) => question.flagged = !question.flagged,
+        }
+    }
+
+    /// Returns whether the item was answered, or graded if a card.
+    pub fn has_answer(&self) -> bool {
+        match self {
+            Item::Question(question) => question.user_selection != SelectionFlags::NONE,
+            Item::Card(card) => !matches!(card.user_grade, CardGrade::Pending),
+            Item::Text(question) => !question.user_answer.is_empty(),
+            Item::Numeric(question) => !question.user_answer.is_empty(),
+            Item::TrueFalse(question) => question.user_answer.is_some(),
+            Item::Ordering(question) => question.is_answered(),
+            Item::Matching(question) => question.user_matches.iter().any(Option::is_some),
+        }
+    }
+
+    /// Clears the answer of the user, so that the item can be answered again.
+    pub fn reset_answer(&mut self) {
+        match self {
+            Item::Question(question) => question.user_selection = SelectionFlags::NONE,
+            Item::Card(card) => card.user_grade = CardGrade::Pending,
+            Item::Text(question) => question.user_answer.clear(),
+            Item::Numeric(question) => question.user_answer.clear(),
+            Item::TrueFalse(question) => question.user_answer = None,
+            Item::Ordering(question) => question.user_order.clear(),
+            Item::Matching(question) => question.user_matches.clear(),
+        }
+    }
+
+    /// Returns the typed answer of items answered with text.
+    pub fn get_user_answer(&self) -> Option<&str> {
+        match self {
+            Item::Text(question) => Some(&question.user_answer),
+            Item::Numeric(question) => Some(&question.user_answer),
+            _ => None,
+        }
+    }
+
+    /// Returns the tags listed in the `tags` field of the item.
+    pub fn get_tags(&self) -> Vec<&str> {
+        self.get_extra()
+            .get("tags")
+            .and_then(|tags| tags.as_array())
+            .map(|tags| tags.iter().filter_map(|tag| tag.as_str()).collect())
+            .unwrap_or_default()
+    }
 }
 
 impl Question {
@@ -358,10 +1481,26 @@ impl Question {
     pub fn has_selection(&self, selection: SelectionFlags) -> bool {
         // `selection` should not be greater than the char that
         // the number of selections indicates.
-        selection.bits() >> self.num_selections() == 0
+        selection
+            .bits()
+            .checked_shr(self.num_selections() as u32)
+            .unwrap_or(0)
+            == 0
     }
 }
 
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn is_exact(scoring: &ScoringPolicy) -> bool {
+    *scoring == ScoringPolicy::Exact
+}
+
 mod selection_flags_serde {
     use super::SelectionFlags;
     use serde::{Deserialize, Deserializer, Serializer};
@@ -370,16 +1509,171 @@ mod selection_flags_serde {
     where
         S: Serializer,
     {
-        serializer.serialize_u8(data.bits())
+        serializer.serialize_u32(data.bits())
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<SelectionFlags, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let bits = u8::deserialize(deserializer)?;
+        // Files written with the former `u8` flags deserialize as-is.
+        let bits = u32::deserialize(deserializer)?;
         SelectionFlags::from_bits(bits).ok_or(serde::de::Error::custom(
             "Error deserializing selection flags",
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exam(questions: &str) -> Exam {
+        serde_json::from_str(&format!("{{\"questions\": [{}]}}", questions)).unwrap()
+    }
+
+    #[test]
+    fn step_order_without_steps() {
+        let exam = exam(r#"{"type": "Ordering", "question": "?", "steps": []}"#);
+        assert_eq!(exam.step_order(0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn step_order_with_one_step() {
+        let exam = exam(r#"{"type": "Ordering", "question": "?", "steps": ["a"]}"#);
+        assert_eq!(exam.step_order(0), vec![0]);
+    }
+
+    #[test]
+    fn steps_start_out_of_order() {
+        let exam = exam(r#"{"type": "Ordering", "question": "?", "steps": ["a", "b"]}"#);
+        assert_eq!(exam.step_order(0), vec![1, 0]);
+    }
+
+    #[test]
+    fn partial_step_order_has_no_answer() {
+        let mut exam = exam(r#"{"type": "Ordering", "question": "?", "steps": ["a", "b"]}"#);
+        if let Some(Item::Ordering(question)) = exam.question_at_mut(0) {
+            question.user_order = vec![1];
+        }
+        assert!(!exam.questions[0].has_answer());
+        if let Some(Item::Ordering(question)) = exam.question_at_mut(0) {
+            question.user_order = vec![1, 0];
+        }
+        assert!(exam.questions[0].has_answer());
+    }
+
+    #[test]
+    fn match_order_without_pairs() {
+        let exam = exam(r#"{"type": "Matching", "question": "?", "pairs": []}"#);
+        assert_eq!(exam.match_order(0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn match_order_with_one_pair() {
+        let exam = exam(
+            r#"{"type": "Matching", "question": "?", "pairs": [{"left": "a", "right": "b"}]}"#,
+        );
+        assert_eq!(exam.match_order(0), vec![0]);
+    }
+
+    #[test]
+    fn unpaired_matching_has_no_answer() {
+        let mut exam = exam(
+            r#"{"type": "Matching", "question": "?", "pairs": [{"left": "a", "right": "b"}]}"#,
+        );
+        if let Some(Item::Matching(question)) = exam.question_at_mut(0) {
+            question.user_matches = vec![None];
+        }
+        assert!(!exam.questions[0].has_answer());
+        if let Some(Item::Matching(question)) = exam.question_at_mut(0) {
+            question.user_matches = vec![Some(0)];
+        }
+        assert!(exam.questions[0].has_answer());
+    }
+
+    /// Returns a question of four options, the first two correct, with the
+    /// options of `selected` chosen.
+    fn question(selected: &[usize]) -> Question {
+        let mut question: Question = serde_json::from_str(
+            r#"{"question": "?", "selections": [
+                {"text": "a", "should_select": true},
+                {"text": "b", "should_select": true},
+                {"text": "c"},
+                {"text": "d"}
+            ], "answer": null}"#,
+        )
+        .unwrap();
+        for index in selected {
+            question.user_selection |= SelectionFlags::from_index(*index).unwrap();
+        }
+        question
+    }
+
+    #[test]
+    fn unanswered_question_has_no_score() {
+        let question = question(&[]);
+        assert_eq!(question.get_score(ScoringPolicy::Partial), None);
+        assert!(matches!(
+            question.get_result(ScoringPolicy::Partial),
+            QuestionResult::Pending
+        ));
+    }
+
+    #[test]
+    fn exact_scoring() {
+        assert_eq!(question(&[0, 1]).get_score(ScoringPolicy::Exact), Some(1.0));
+        assert_eq!(question(&[0]).get_score(ScoringPolicy::Exact), Some(0.0));
+    }
+
+    #[test]
+    fn partial_scoring() {
+        assert_eq!(question(&[0]).get_score(ScoringPolicy::Partial), Some(0.75));
+        assert_eq!(
+            question(&[0, 2]).get_score(ScoringPolicy::Partial),
+            Some(0.5)
+        );
+        assert!(matches!(
+            question(&[0]).get_result(ScoringPolicy::Partial),
+            QuestionResult::Partial
+        ));
+    }
+
+    #[test]
+    fn negative_scoring() {
+        assert_eq!(question(&[0]).get_score(ScoringPolicy::Negative), Some(0.5));
+        assert_eq!(
+            question(&[0, 2]).get_score(ScoringPolicy::Negative),
+            Some(0.0)
+        );
+        assert_eq!(
+            question(&[2, 3]).get_score(ScoringPolicy::Negative),
+            Some(-1.0)
+        );
+        assert!(matches!(
+            question(&[0, 1, 2, 3]).get_result(ScoringPolicy::Negative),
+            QuestionResult::Wrong
+        ));
+    }
+
+    #[test]
+    fn weighted_scoring() {
+        let mut question = question(&[0]);
+        question.points = Some(4.0);
+        question.scoring = Some(ScoringPolicy::Partial);
+        // The policy of the question overrides that of the exam
+        assert_eq!(question.get_score(ScoringPolicy::Exact), Some(3.0));
+        assert_eq!(question.max_points(), 4.0);
+    }
+
+    #[test]
+    fn exact_scoring_is_not_saved() {
+        let mut exam = exam("");
+        let json = serde_json::to_value(&exam).unwrap();
+        assert!(json.get("scoring").is_none());
+
+        exam.scoring = ScoringPolicy::Negative;
+        let json = serde_json::to_value(&exam).unwrap();
+        assert_eq!(json["scoring"], "Negative");
+    }
+}
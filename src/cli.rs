@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: exhaust [OPTIONS] [EXAM]
+
+Arguments:
+  [EXAM]                     Exam file to open directly (.json, .exhaust or .gz)
+
+Options:
+  -a, --autosave             Open the exam with autosave enabled
+  -q, --question <N>         Start EXAM at the N-th question (1-based)
+  -t, --time-limit <MINUTES> Take EXAM with a time limit
+  -s, --shuffle              Shuffle questions and selections
+      --seed <SEED>          Shuffle EXAM with SEED instead of a random one
+  -c, --config <FILE>        Use FILE instead of the default exhaust.json
+  -d, --directory <DIR>      Start the file browser in DIR
+  -h, --help                 Print this help message
+  -V, --version              Print version information";
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub exam: Option<PathBuf>,
+    pub autosave: bool,
+    pub question: Option<usize>,
+    /// Time limit in seconds
+    pub time_limit: Option<u64>,
+    pub shuffle: bool,
+    pub seed: Option<u64>,
+    pub config: Option<PathBuf>,
+    pub directory: Option<PathBuf>,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    /// Parses command line arguments, excluding the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, String> {
+        let mut result = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // Split `--flag=value` into the flag and its inline value
+            let (flag, inline_value) = match arg.starts_with("--") {
+                true => match arg.find('=') {
+                    Some(pos) => (arg[..pos].to_owned(), Some(arg[pos + 1..].to_owned())),
+                    None => (arg.clone(), None),
+                },
+                false => (arg.clone(), None),
+            };
+            let mut value = |name: &str| -> Result<String, String> {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(format!("Missing value for {}", name))
+            };
+
+            match flag.as_str() {
+                "-a" | "--autosave" => result.autosave = true,
+                "-q" | "--question" => {
+                    let raw = value(&flag)?;
+                    let question = raw
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|question| *question > 0)
+                        .ok_or(format!("Invalid question number: {}", raw))?;
+                    result.question = Some(question);
+                }
+                "-t" | "--time-limit" => {
+                    let raw = value(&flag)?;
+                    let time_limit = raw
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|time_limit| *time_limit > 0)
+                        .and_then(|time_limit| time_limit.checked_mul(60))
+                        .ok_or(format!("Invalid time limit: {}", raw))?;
+                    result.time_limit = Some(time_limit);
+                }
+                "-s" | "--shuffle" => result.shuffle = true,
+                "--seed" => {
+                    let raw = value(&flag)?;
+                    let seed = raw
+                        .parse::<u64>()
+                        .map_err(|_| format!("Invalid seed: {}", raw))?;
+                    result.seed = Some(seed);
+                }
+                "-c" | "--config" => result.config = Some(value(&flag)?.into()),
+                "-d" | "--directory" => result.directory = Some(value(&flag)?.into()),
+                "-h" | "--help" => result.help = true,
+                "-V" | "--version" => result.version = true,
+                _ if flag.starts_with('-') && flag != "-" => {
+                    return Err(format!("Unknown option: {}", flag));
+                }
+                _ => match result.exam {
+                    None => result.exam = Some(arg.into()),
+                    Some(_) => return Err(format!("Unexpected argument: {}", arg)),
+                },
+            }
+        }
+        // These options only apply to the exam given on the command line
+        if result.exam.is_none() {
+            if result.question.is_some() {
+                return Err("--question requires an EXAM".to_owned());
+            }
+            if result.time_limit.is_some() {
+                return Err("--time-limit requires an EXAM".to_owned());
+            }
+            if result.seed.is_some() {
+                return Err("--seed requires an EXAM".to_owned());
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn time_limit_in_seconds() {
+        assert_eq!(
+            parse(&["-t", "2", "exam.json"]).unwrap().time_limit,
+            Some(120)
+        );
+        assert_eq!(
+            parse(&["--time-limit=1", "exam.json"]).unwrap().time_limit,
+            Some(60)
+        );
+    }
+
+    #[test]
+    fn time_limit_overflow() {
+        assert!(parse(&["-t", &u64::MAX.to_string(), "exam.json"]).is_err());
+        assert!(parse(&["-t", "0", "exam.json"]).is_err());
+    }
+
+    #[test]
+    fn question_and_time_limit_require_exam() {
+        assert!(parse(&["-q", "3"]).is_err());
+        assert!(parse(&["-t", "10"]).is_err());
+        assert_eq!(parse(&["-q", "3", "exam.json"]).unwrap().question, Some(3));
+    }
+
+    #[test]
+    fn seed_requires_exam() {
+        assert!(parse(&["--seed", "42"]).is_err());
+        assert_eq!(
+            parse(&["--seed", "42", "exam.json"]).unwrap().seed,
+            Some(42)
+        );
+    }
+}
@@ -1,24 +1,75 @@
 use crate::app::Exam;
-use crate::app::{AppRoute, OpenMode, SelectionFlags};
+use crate::app::{AppRoute, CardGrade, OpenMode, SelectionFlags};
+use crate::finder::ExamEntry;
+use crate::preview::Image;
 
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
-use crossterm::event::{read, Event, KeyEvent};
+use crossterm::event::{read, Event, KeyEvent, MouseEvent};
+
+/// Interval between two `Messages::Tick`s. The exam timer counts in ticks.
+pub const TICK_RATE: Duration = Duration::from_secs(1);
 
 #[derive(Debug)]
 pub enum UpdateQuestionIndexEvent {
     Next,
     Prev,
+    NextFlagged,
+    PrevFlagged,
     Set(usize),
 }
 
+#[derive(Debug)]
+pub enum UpdateAnswerInputEvent {
+    Open,
+    Push(char),
+    Pop,
+    Cancel,
+    Submit,
+}
+
+#[derive(Debug)]
+pub enum UpdateSearchEvent {
+    Open,
+    Push(char),
+    Pop,
+    Cancel,
+    Submit,
+}
+
+#[derive(Debug)]
+pub enum UpdateStepOrderEvent {
+    /// Moves the cursor
+    Next,
+    Prev,
+    /// Moves the step under the cursor
+    MoveDown,
+    MoveUp,
+    /// Accepts the displayed order as the answer
+    Submit,
+}
+
+#[derive(Debug)]
+pub enum UpdateMatchingEvent {
+    /// Moves the cursor
+    Next,
+    Prev,
+    /// Pairs the left entry under the cursor with the given right entry
+    Assign(usize),
+    Clear,
+}
+
 #[derive(Debug)]
 pub enum UpdateListSelectedEvent {
     Next,
     Prev,
     Home,
     End,
+    /// Selects the entry at an index, e.g. when clicked
+    To(usize),
 }
 
 #[derive(Clone, Debug)]
@@ -31,6 +82,9 @@ pub enum QuitAction {
 pub enum SaveModalState {
     Hidden,
     ShowSave,
+    ShowSaveAs(String),
+    /// Asks before replacing an existing file with a save as
+    ShowOverwrite(PathBuf),
     ShowQuit(QuitAction),
 }
 
@@ -39,6 +93,9 @@ pub enum SaveModalActions {
     Open(SaveModalState),
     Quit(QuitAction),
     Okay,
+    SaveAs(PathBuf),
+    /// Saves as an existing file, once confirmed
+    Overwrite(PathBuf),
     Cancel,
 }
 
@@ -47,26 +104,111 @@ pub enum AssetsModalActions {
     Open,
     Select(UpdateListSelectedEvent),
     OpenFile,
+    TogglePreview,
+    View(TextViewActions),
+    Close,
+}
+
+#[derive(Debug)]
+pub enum TextViewActions {
+    Scroll(isize),
+    PageDown,
+    PageUp,
+    Home,
+    End,
+    Search(UpdateSearchEvent),
+    NextMatch,
+    PrevMatch,
+    /// Returns to the list of assets
+    Close,
+}
+
+#[derive(Debug)]
+pub enum ExamSearchActions {
+    Search(UpdateSearchEvent),
+    NextMatch,
+    PrevMatch,
+    /// Jumps to a result by its position in the list
+    Select(usize),
+    /// Closes the list of results
+    Close,
+}
+
+#[derive(Debug)]
+pub enum FinderActions {
+    Open,
+    /// The exams found under a directory
+    Indexed(PathBuf, Vec<ExamEntry>),
+    Push(char),
+    Pop,
+    Select(UpdateListSelectedEvent),
+    OpenFile,
     Close,
 }
 
+#[derive(Debug)]
+pub enum ErrorModalActions {
+    Close,
+}
+
+/// The action run once confirmed
+#[derive(Clone, Debug)]
+pub enum ConfirmAction {
+    StartStudy,
+}
+
+#[derive(Debug)]
+pub enum ConfirmModalActions {
+    Okay,
+    Cancel,
+}
+
 #[derive(Debug)]
 pub enum Messages {
     Input(KeyEvent),
+    Mouse(MouseEvent),
     Resize,
     ChangeRoute(AppRoute),
     UpdateQuestionIndex(UpdateQuestionIndexEvent),
     ScrollQuestion(u16),
     UpdateHomeSelected(UpdateListSelectedEvent),
+    Finder(FinderActions),
+    UpdateResultsSelected(UpdateListSelectedEvent),
+    ExportReport,
+    ReportExported(PathBuf),
     UpdateJumpboxValue(u16),
     ToggleSelection(SelectionFlags),
+    FlipCard,
+    GradeCard(CardGrade),
+    ToggleFlag,
+    UpdateAnswerInput(UpdateAnswerInputEvent),
+    SetTrueFalse(bool),
+    UpdateStepOrder(UpdateStepOrderEvent),
+    UpdateMatching(UpdateMatchingEvent),
+    ExamSearch(ExamSearchActions),
     LoadFile,
+    LoadFailed(String),
     SaveModalAction(SaveModalActions),
     AssetsModalAction(AssetsModalActions),
+    /// An image asset decoded for a preview
+    PreviewLoaded(PathBuf, Result<Image, String>),
+    ErrorModalAction(ErrorModalActions),
+    ConfirmModalAction(ConfirmModalActions),
     UnsavedChanges(bool),
-    FileLoaded(Exam),
+    SaveFailed(String),
+    /// The exam was saved to another file, which is now its source
+    SavedAs(PathBuf),
+    FileLoaded(PathBuf, Box<Exam>),
     SetOpenMode(OpenMode),
+    ToggleShuffle,
     ToggleExamResult,
+    ToggleReview,
+    ToggleFlaggedReview,
+    ToggleStudy,
+    /// Enters study mode, resetting the answers of due items reviewed before
+    StartStudy,
+    ResetReviewItems,
+    Tick,
     Quit,
 }
 
@@ -76,6 +218,7 @@ pub struct Events {
     pub tx: mpsc::Sender<Messages>,
     rx: mpsc::Receiver<Messages>,
     _input_handle: thread::JoinHandle<()>,
+    _tick_handle: thread::JoinHandle<()>,
 }
 
 impl Events {
@@ -87,7 +230,12 @@ impl Events {
                 if let Ok(event) = read() {
                     match event {
                         Event::Key(keyevent) => {
-                            if let Err(_) = tx.send(Messages::Input(keyevent)) {
+                            if tx.send(Messages::Input(keyevent)).is_err() {
+                                return;
+                            }
+                        }
+                        Event::Mouse(mouseevent) => {
+                            if tx.send(Messages::Mouse(mouseevent)).is_err() {
                                 return;
                             }
                         }
@@ -96,15 +244,24 @@ impl Events {
                                 return;
                             }
                         }
-                        _ => {}
                     }
                 }
             })
         };
+        let _tick_handle = {
+            let tx = tx.clone();
+            thread::spawn(move || loop {
+                thread::sleep(TICK_RATE);
+                if tx.send(Messages::Tick).is_err() {
+                    return;
+                }
+            })
+        };
         Events {
             tx,
             rx,
             _input_handle,
+            _tick_handle,
         }
     }
 
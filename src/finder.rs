@@ -0,0 +1,240 @@
+/*
+ * finder.rs
+ *
+ * The fuzzy finder of exams on the home screen.
+ *
+ * Exams are looked up recursively under the current directory, down to a
+ * depth limit and skipping hidden directories, and read once for their
+ * title, number of items and progress. The query is split into words, each
+ * of which must appear in the relative path or the title with its letters in
+ * order. Letters matched in a row or at the start of a word rank higher.
+ */
+use crate::app::{Exam, HasQuestionResult, QuestionResult};
+use crate::reducer;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+use tui::widgets::ListState;
+
+#[derive(Default)]
+pub struct Finder {
+    pub query: String,
+    /// The exams found, or `None` while they are being read
+    pub entries: Option<Vec<ExamEntry>>,
+    /// Positions of the entries matching the query, best first
+    pub matches: Vec<usize>,
+    pub list_state: ListState,
+}
+
+impl Finder {
+    pub fn set_entries(&mut self, entries: Vec<ExamEntry>) {
+        self.entries = Some(entries);
+        self.update_matches();
+    }
+
+    /// Ranks the entries by the query, and selects the best match.
+    pub fn update_matches(&mut self) {
+        let entries = match &self.entries {
+            Some(entries) => entries,
+            None => return,
+        };
+        let mut scores: Vec<(i64, usize)> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, entry)| Some((entry.score(&self.query)?, pos)))
+            .collect();
+        // Entries are sorted by path, which breaks ties
+        scores.sort_by(|(a, a_pos), (b, b_pos)| b.cmp(a).then(a_pos.cmp(b_pos)));
+
+        self.matches = scores.into_iter().map(|(_, pos)| pos).collect();
+        self.list_state.select(match self.matches.is_empty() {
+            true => None,
+            false => Some(0),
+        });
+    }
+
+    pub fn selected(&self) -> Option<&ExamEntry> {
+        let pos = *self.matches.get(self.list_state.selected()?)?;
+        self.entries.as_ref()?.get(pos)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExamEntry {
+    pub path: PathBuf,
+    /// Path relative to the directory searched
+    pub name: String,
+    /// Read from the file, `None` if it is not an exam
+    pub meta: Option<ExamMeta>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExamMeta {
+    pub title: Option<String>,
+    pub num_questions: usize,
+    pub num_answered: usize,
+}
+
+impl ExamMeta {
+    fn new(exam: &Exam) -> Self {
+        let num_answered = exam
+            .questions
+            .iter()
+            .filter(|item| !matches!(item.get_result(exam.scoring), QuestionResult::Pending))
+            .count();
+        ExamMeta {
+            title: exam.title.clone(),
+            num_questions: exam.num_questions(),
+            num_answered,
+        }
+    }
+}
+
+impl ExamEntry {
+    /// Returns the score of the entry for `query`, or `None` if some word of
+    /// the query is not found.
+    fn score(&self, query: &str) -> Option<i64> {
+        let title = self
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.title.as_deref())
+            .unwrap_or("");
+        query.split_whitespace().try_fold(0, |score, word| {
+            let best = match (fuzzy_score(word, &self.name), fuzzy_score(word, title)) {
+                (Some(a), Some(b)) => a.max(b),
+                (a, b) => a.or(b)?,
+            };
+            Some(score + best)
+        })
+    }
+}
+
+/// Reads the exams under `root`, at most `max_depth` directories deep.
+pub fn index(root: &Path, max_depth: usize) -> Vec<ExamEntry> {
+    let mut paths = vec![];
+    find_exams(root, max_depth, &mut paths);
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .into_owned();
+            let meta = reducer::load_exam(&path)
+                .ok()
+                .map(|exam| ExamMeta::new(&exam));
+            ExamEntry { path, name, meta }
+        })
+        .collect()
+}
+
+fn find_exams(dir: &Path, depth: usize, paths: &mut Vec<PathBuf>) {
+    let entries = match read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        // Symbolic links to directories are not followed
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => {
+                if depth > 0 && !hidden {
+                    find_exams(&path, depth - 1, paths);
+                }
+            }
+            Ok(_) => match path.extension().and_then(|ext| ext.to_str()) {
+                Some("json") | Some("exhaust") | Some("gz") => paths.push(path),
+                _ => {}
+            },
+            Err(_) => {}
+        }
+    }
+}
+
+/// Scores how well `word` matches `text` as a subsequence, ignoring case.
+fn fuzzy_score(word: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut start = 0;
+    let mut last_match: Option<usize> = None;
+    for c in word.chars().flat_map(char::to_lowercase) {
+        let pos = start + text[start..].iter().position(|t| *t == c)?;
+        score += 1;
+        if pos > 0 && last_match == Some(pos - 1) {
+            score += 4;
+        }
+        if pos == 0 || !text[pos - 1].is_alphanumeric() {
+            score += 2;
+        }
+        // Gaps cost a little, so that tight matches win
+        if let Some(last) = last_match {
+            score -= ((pos - last - 1) as i64).min(3);
+        }
+        last_match = Some(pos);
+        start = pos + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, title: Option<&str>) -> ExamEntry {
+        ExamEntry {
+            path: PathBuf::from(name),
+            name: name.to_owned(),
+            meta: Some(ExamMeta {
+                title: title.map(str::to_owned),
+                num_questions: 1,
+                num_answered: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn letters_must_appear_in_order() {
+        assert!(fuzzy_score("net", "networks.json").is_some());
+        assert!(fuzzy_score("NWK", "networks.json").is_some());
+        assert_eq!(fuzzy_score("ten", "networks.json"), None);
+        assert_eq!(fuzzy_score("x", ""), None);
+    }
+
+    #[test]
+    fn tight_matches_rank_higher() {
+        let in_a_row = fuzzy_score("net", "networks.json").unwrap();
+        let scattered = fuzzy_score("net", "n_e_t.json").unwrap();
+        assert!(in_a_row > scattered);
+        let word_start = fuzzy_score("os", "linux/os.json").unwrap();
+        let inside = fuzzy_score("os", "kosmos.json").unwrap();
+        assert!(word_start > inside);
+    }
+
+    #[test]
+    fn every_word_must_match_the_name_or_title() {
+        let entry = entry("cs/networks.json", Some("Routing basics"));
+        assert!(entry.score("net rout").is_some());
+        assert!(entry.score("net algebra").is_none());
+        assert_eq!(entry.score(""), Some(0));
+    }
+
+    #[test]
+    fn matches_are_ranked_and_the_best_selected() {
+        let mut finder = Finder {
+            query: "os".to_owned(),
+            ..Finder::default()
+        };
+        finder.set_entries(vec![
+            entry("kosmos.json", None),
+            entry("maths.json", None),
+            entry("os.json", None),
+        ]);
+        assert_eq!(finder.matches, vec![2, 0]);
+        assert_eq!(
+            finder.selected().map(|entry| entry.name.as_str()),
+            Some("os.json")
+        );
+    }
+}
@@ -0,0 +1,291 @@
+/*
+ * highlight.rs
+ *
+ * Syntax highlighting of fenced code blocks.
+ *
+ * Lines are split into tokens that are classified with the keywords of common
+ * languages. This is far from a parser, but is enough for the snippets of an
+ * exam. Tokens also tell where a long line of code can be wrapped.
+ */
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    Literal,
+    Number,
+    String,
+    Comment,
+    Type,
+}
+
+struct Syntax {
+    /// Names of the language after the opening fence
+    names: &'static [&'static str],
+    /// Whitespace separated words
+    keywords: &'static str,
+    literals: &'static str,
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    case_insensitive: bool,
+}
+
+const SYNTAXES: &[Syntax] = &[
+    Syntax {
+        names: &["rust", "rs"],
+        keywords: "\
+            as async await break const continue crate dyn else enum extern fn for if \
+            impl in let loop match mod move mut pub ref return self static struct super \
+            trait type unsafe use where while",
+        literals: "true false None Some Ok Err",
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        // Single quotes also start lifetimes
+        quotes: &['"'],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["python", "py", "python3"],
+        keywords: "\
+            and as assert async await break class continue def del elif else except \
+            finally for from global if import in is lambda nonlocal not or pass raise \
+            return try while with yield",
+        literals: "True False None",
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["javascript", "js", "jsx", "typescript", "ts", "tsx"],
+        keywords: "\
+            async await break case catch class const continue default delete do else \
+            enum export extends finally for function if implements import in instanceof \
+            interface let new of return super switch this throw try type typeof var void \
+            while yield",
+        literals: "true false null undefined NaN",
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["c", "h", "cpp", "c++", "cc", "hpp"],
+        keywords: "\
+            auto bool break case char class const continue default delete do double else \
+            enum extern float for goto if inline int long namespace new private \
+            protected public register return short signed sizeof static struct switch \
+            template this typedef typename union unsigned using virtual void volatile \
+            while",
+        literals: "true false NULL nullptr",
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["java"],
+        keywords: "\
+            abstract boolean break byte case catch char class continue default do double \
+            else enum extends final finally float for if implements import instanceof \
+            int interface long new package private protected public return short static \
+            super switch synchronized this throw throws try var void volatile while",
+        literals: "true false null",
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["go", "golang"],
+        keywords: "\
+            break case chan const continue default defer else fallthrough for func go \
+            goto if import interface map package range return select struct switch type \
+            var",
+        literals: "true false nil iota",
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["sh", "bash", "shell", "zsh", "console"],
+        keywords: "\
+            case do done echo elif else esac exit export fi for function if in local \
+            return then while",
+        literals: "true false",
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        case_insensitive: false,
+    },
+    Syntax {
+        names: &["sql"],
+        keywords: "\
+            alter and as by create delete distinct drop from group having in inner \
+            insert into is join left limit not on or order outer right select set table \
+            union update values where",
+        literals: "null true false",
+        line_comments: &["--"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['\''],
+        case_insensitive: true,
+    },
+    Syntax {
+        names: &["json"],
+        keywords: "\
+",
+        literals: "true false null",
+        line_comments: &[],
+        block_comment: None,
+        quotes: &['"'],
+        case_insensitive: false,
+    },
+];
+
+/// Highlights the lines of a code block one by one, keeping track of block
+/// comments across lines.
+pub struct Highlighter {
+    syntax: Option<&'static Syntax>,
+    in_block_comment: bool,
+}
+
+impl Highlighter {
+    /// Creates a highlighter for the language named after the opening fence.
+    /// Unknown languages are split into tokens without being classified.
+    pub fn new(lang: &str) -> Self {
+        // Attributes may follow the name, as in ```rust,ignore
+        let lang = lang
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .find(|name| !name.is_empty())
+            .unwrap_or("")
+            .to_lowercase();
+        Highlighter {
+            syntax: SYNTAXES
+                .iter()
+                .find(|syntax| syntax.names.contains(&lang.as_str())),
+            in_block_comment: false,
+        }
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.syntax.is_some()
+    }
+
+    pub fn highlight_line(&mut self, line: &str) -> Vec<(String, TokenKind)> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut tokens = vec![];
+        let mut i = 0;
+
+        while i < chars.len() {
+            let (len, kind) = self.next_token(&chars[i..]);
+            tokens.push((chars[i..i + len].iter().collect(), kind));
+            i += len;
+        }
+        tokens
+    }
+
+    /// Returns the length and kind of the token at the start of `chars`.
+    fn next_token(&mut self, chars: &[char]) -> (usize, TokenKind) {
+        let c = chars[0];
+        let run = |pred: &dyn Fn(char) -> bool| chars.iter().take_while(|c| pred(**c)).count();
+
+        if c.is_whitespace() {
+            return (run(&|c: char| c.is_whitespace()), TokenKind::Plain);
+        }
+        let syntax = match self.syntax {
+            Some(syntax) => syntax,
+            // Words are kept together, so that lines are wrapped between them
+            None => match c.is_alphanumeric() || c == '_' {
+                true => {
+                    return (
+                        run(&|c: char| c.is_alphanumeric() || c == '_'),
+                        TokenKind::Plain,
+                    )
+                }
+                false => return (1, TokenKind::Plain),
+            },
+        };
+
+        // Block comments
+        if let Some((start, end)) = syntax.block_comment {
+            if self.in_block_comment || starts_with(chars, start) {
+                let from = match self.in_block_comment {
+                    true => 0,
+                    false => start.len(),
+                };
+                self.in_block_comment = true;
+                return match find(chars, from, end) {
+                    Some(pos) => {
+                        self.in_block_comment = false;
+                        (pos + end.len(), TokenKind::Comment)
+                    }
+                    None => (chars.len(), TokenKind::Comment),
+                };
+            }
+        }
+
+        // Line comments
+        if syntax
+            .line_comments
+            .iter()
+            .any(|start| starts_with(chars, start))
+        {
+            return (chars.len(), TokenKind::Comment);
+        }
+
+        // Strings end with the same quote, unless escaped
+        if syntax.quotes.contains(&c) {
+            let mut len = 1;
+            while len < chars.len() {
+                match chars[len] {
+                    '\\' => len += 2,
+                    quote if quote == c => return (len + 1, TokenKind::String),
+                    _ => len += 1,
+                }
+            }
+            return (chars.len(), TokenKind::String);
+        }
+
+        if c.is_ascii_digit() {
+            let len = run(&|c: char| c.is_alphanumeric() || c == '.' || c == '_');
+            return (len, TokenKind::Number);
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let len = run(&|c: char| c.is_alphanumeric() || c == '_');
+            let word: String = chars[..len].iter().collect();
+            let word = match syntax.case_insensitive {
+                true => word.to_lowercase(),
+                false => word,
+            };
+            let kind = if contains_word(syntax.keywords, &word) {
+                TokenKind::Keyword
+            } else if contains_word(syntax.literals, &word) {
+                TokenKind::Literal
+            } else if c.is_uppercase() {
+                TokenKind::Type
+            } else {
+                TokenKind::Plain
+            };
+            return (len, kind);
+        }
+
+        (1, TokenKind::Plain)
+    }
+}
+
+fn contains_word(words: &str, word: &str) -> bool {
+    words.split_whitespace().any(|w| w == word)
+}
+
+fn starts_with(chars: &[char], pattern: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    chars.starts_with(&pattern)
+}
+
+fn find(chars: &[char], from: usize, pattern: &str) -> Option<usize> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    (from..chars.len()).find(|i| chars[*i..].starts_with(&pattern))
+}
@@ -0,0 +1,604 @@
+/*
+ * keymap.rs
+ *
+ * Key bindings of named actions, configurable in the `keymap` section of
+ * `exhaust.json`.
+ *
+ * Every action belongs to a scope, the widget that handles it, and is named
+ * `scope.action` in the config, e.g. `"home.down": ["j", "Down"]`. Bindings
+ * given in the config replace the defaults of their action. Keys bound twice
+ * in scopes that handle keys at the same time are rejected when loading.
+ *
+ * Keys are written as a single character (`"j"`, `"G"`), or a key name
+ * (`"Enter"`, `"Space"`, `"PageDown"`, `"F1"`), optionally after `Ctrl-`
+ * (or `^`), `Alt-` and `Shift-`.
+ */
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Keys handled before anything else
+    Global,
+    Home,
+    Results,
+    /// Keys handled everywhere in an exam
+    Exam,
+    /// Items with a scrollable question
+    Item,
+    Card,
+    Text,
+    TrueFalse,
+    Ordering,
+    Matching,
+    /// The results of a search across the exam
+    Search,
+    /// The exam finder of the home screen, along with the input keys
+    Finder,
+    /// Editing keys of text inputs
+    Input,
+    Assets,
+    TextView,
+    Save,
+    Error,
+    Confirm,
+}
+
+impl Scope {
+    fn name(self) -> &'static str {
+        match self {
+            Scope::Global => "global",
+            Scope::Home => "home",
+            Scope::Results => "results",
+            Scope::Exam => "exam",
+            Scope::Item => "item",
+            Scope::Card => "card",
+            Scope::Text => "text",
+            Scope::TrueFalse => "true_false",
+            Scope::Ordering => "ordering",
+            Scope::Matching => "matching",
+            Scope::Search => "search",
+            Scope::Finder => "finder",
+            Scope::Input => "input",
+            Scope::Assets => "assets",
+            Scope::TextView => "text_view",
+            Scope::Save => "save",
+            Scope::Error => "error",
+            Scope::Confirm => "confirm",
+        }
+    }
+}
+
+/// Scopes that handle keys at the same time, and so cannot share keys.
+const ACTIVE_SCOPES: &[&[Scope]] = &[
+    &[Scope::Global, Scope::Home],
+    &[Scope::Global, Scope::Results],
+    &[Scope::Global, Scope::Exam, Scope::Item],
+    &[Scope::Global, Scope::Exam, Scope::Item, Scope::Card],
+    &[Scope::Global, Scope::Exam, Scope::Item, Scope::Text],
+    &[Scope::Global, Scope::Exam, Scope::Item, Scope::TrueFalse],
+    &[Scope::Global, Scope::Exam, Scope::Ordering],
+    &[Scope::Global, Scope::Exam, Scope::Matching],
+    &[Scope::Global, Scope::Search],
+    // The global keys are typed as text into inputs
+    &[Scope::Input],
+    &[Scope::Input, Scope::Finder],
+    &[Scope::Global, Scope::Assets],
+    &[Scope::Global, Scope::TextView],
+    &[Scope::Global, Scope::Save],
+    &[Scope::Global, Scope::Error],
+    &[Scope::Global, Scope::Confirm],
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    QuitProgram,
+    Quit,
+    Back,
+    Open,
+    Close,
+    Cancel,
+    Down,
+    Up,
+    Top,
+    Bottom,
+    PageDown,
+    PageUp,
+    ToggleAutosave,
+    ToggleShuffle,
+    Export,
+    Save,
+    ShowResults,
+    ToggleReview,
+    ToggleFlaggedReview,
+    ResetReview,
+    ToggleStudy,
+    NextItem,
+    PrevItem,
+    NextFlagged,
+    PrevFlagged,
+    OpenAssets,
+    ToggleAnswer,
+    ToggleFlag,
+    Known,
+    Unknown,
+    EditAnswer,
+    AnswerTrue,
+    AnswerFalse,
+    MoveDown,
+    MoveUp,
+    Submit,
+    Unpair,
+    DeleteChar,
+    TogglePreview,
+    Search,
+    NextMatch,
+    PrevMatch,
+    Okay,
+    SaveAs,
+}
+
+/// The actions of every scope with their names and default keys.
+const DEFAULT_BINDINGS: &[(Scope, Action, &str, &[&str])] = &[
+    (Scope::Global, Action::QuitProgram, "quit_program", &["Q"]),
+    (Scope::Home, Action::Open, "open", &["Enter"]),
+    (Scope::Home, Action::Down, "down", &["j", "Down"]),
+    (Scope::Home, Action::Up, "up", &["k", "Up"]),
+    (Scope::Home, Action::Top, "top", &["g"]),
+    (Scope::Home, Action::Bottom, "bottom", &["G"]),
+    (
+        Scope::Home,
+        Action::ToggleAutosave,
+        "toggle_autosave",
+        &["a"],
+    ),
+    (Scope::Home, Action::ToggleShuffle, "toggle_shuffle", &["s"]),
+    (Scope::Home, Action::Search, "find", &["/"]),
+    (Scope::Home, Action::Quit, "quit", &["q"]),
+    (Scope::Results, Action::Back, "back", &["q", "Esc"]),
+    (Scope::Results, Action::Open, "open", &["Enter"]),
+    (Scope::Results, Action::Down, "down", &["j", "Down"]),
+    (Scope::Results, Action::Up, "up", &["k", "Up"]),
+    (Scope::Results, Action::Top, "top", &["g"]),
+    (Scope::Results, Action::Bottom, "bottom", &["G"]),
+    (Scope::Results, Action::Export, "export", &["e"]),
+    (Scope::Exam, Action::Quit, "quit", &["q"]),
+    (Scope::Exam, Action::Save, "save", &["^s"]),
+    (Scope::Exam, Action::ShowResults, "show_results", &["^r"]),
+    (Scope::Exam, Action::ToggleReview, "toggle_review", &["^w"]),
+    (
+        Scope::Exam,
+        Action::ToggleFlaggedReview,
+        "toggle_flagged_review",
+        &["^g"],
+    ),
+    (Scope::Exam, Action::ResetReview, "reset_review", &["^x"]),
+    (Scope::Exam, Action::ToggleStudy, "toggle_study", &["^d"]),
+    (Scope::Exam, Action::NextItem, "next_item", &["n", ">"]),
+    (Scope::Exam, Action::PrevItem, "prev_item", &["p", "<"]),
+    (Scope::Exam, Action::NextFlagged, "next_flagged", &["]"]),
+    (Scope::Exam, Action::PrevFlagged, "prev_flagged", &["["]),
+    (Scope::Exam, Action::OpenAssets, "open_assets", &["t"]),
+    (
+        Scope::Exam,
+        Action::ToggleAnswer,
+        "toggle_answer",
+        &["Space"],
+    ),
+    (Scope::Exam, Action::ToggleFlag, "toggle_flag", &["^f"]),
+    (Scope::Exam, Action::Search, "search", &["/"]),
+    (Scope::Item, Action::Down, "scroll_down", &["j"]),
+    (Scope::Item, Action::Up, "scroll_up", &["k"]),
+    (Scope::Card, Action::Known, "known", &["y", "Y"]),
+    (Scope::Card, Action::Unknown, "unknown", &["x", "X"]),
+    (
+        Scope::Text,
+        Action::EditAnswer,
+        "edit_answer",
+        &["Enter", "i"],
+    ),
+    (Scope::TrueFalse, Action::AnswerTrue, "answer_true", &["T"]),
+    (
+        Scope::TrueFalse,
+        Action::AnswerFalse,
+        "answer_false",
+        &["F"],
+    ),
+    (Scope::Ordering, Action::Down, "down", &["j", "Down"]),
+    (Scope::Ordering, Action::Up, "up", &["k", "Up"]),
+    (Scope::Ordering, Action::MoveDown, "move_down", &["J"]),
+    (Scope::Ordering, Action::MoveUp, "move_up", &["K"]),
+    (Scope::Ordering, Action::Submit, "submit", &["Enter"]),
+    (Scope::Matching, Action::Down, "down", &["j", "Down"]),
+    (Scope::Matching, Action::Up, "up", &["k", "Up"]),
+    (
+        Scope::Matching,
+        Action::Unpair,
+        "unpair",
+        &["Backspace", "Delete"],
+    ),
+    (
+        Scope::Search,
+        Action::NextMatch,
+        "next_match",
+        &["n", "j", "Down"],
+    ),
+    (
+        Scope::Search,
+        Action::PrevMatch,
+        "prev_match",
+        &["N", "k", "Up"],
+    ),
+    (Scope::Search, Action::Search, "search", &["/"]),
+    (
+        Scope::Search,
+        Action::Close,
+        "close",
+        &["Enter", "Esc", "q"],
+    ),
+    (Scope::Finder, Action::Down, "down", &["Down", "^n"]),
+    (Scope::Finder, Action::Up, "up", &["Up", "^p"]),
+    (Scope::Input, Action::Submit, "submit", &["Enter"]),
+    (Scope::Input, Action::Cancel, "cancel", &["Esc"]),
+    (
+        Scope::Input,
+        Action::DeleteChar,
+        "delete_char",
+        &["Backspace"],
+    ),
+    (Scope::Assets, Action::Open, "open", &["Enter"]),
+    (Scope::Assets, Action::Close, "close", &["c", "C", "Esc"]),
+    (
+        Scope::Assets,
+        Action::TogglePreview,
+        "toggle_preview",
+        &["p", "P"],
+    ),
+    (Scope::Assets, Action::Down, "down", &["j", "Down"]),
+    (Scope::Assets, Action::Up, "up", &["k", "Up"]),
+    (Scope::Assets, Action::Top, "top", &["g"]),
+    (Scope::Assets, Action::Bottom, "bottom", &["G"]),
+    (Scope::TextView, Action::Down, "down", &["j", "Down"]),
+    (Scope::TextView, Action::Up, "up", &["k", "Up"]),
+    (
+        Scope::TextView,
+        Action::PageDown,
+        "page_down",
+        &["Space", "PageDown"],
+    ),
+    (Scope::TextView, Action::PageUp, "page_up", &["b", "PageUp"]),
+    (Scope::TextView, Action::Top, "top", &["g", "Home"]),
+    (Scope::TextView, Action::Bottom, "bottom", &["G", "End"]),
+    (Scope::TextView, Action::Search, "search", &["/"]),
+    (Scope::TextView, Action::NextMatch, "next_match", &["n"]),
+    (Scope::TextView, Action::PrevMatch, "prev_match", &["N"]),
+    (Scope::TextView, Action::Close, "close", &["q", "Esc"]),
+    (Scope::Save, Action::Okay, "okay", &["o", "O"]),
+    (Scope::Save, Action::SaveAs, "save_as", &["a", "A"]),
+    (Scope::Save, Action::Quit, "quit", &["q"]),
+    (Scope::Save, Action::Cancel, "cancel", &["c", "C", "Esc"]),
+    (
+        Scope::Error,
+        Action::Close,
+        "close",
+        &["o", "O", "Enter", "Esc"],
+    ),
+    (Scope::Confirm, Action::Okay, "okay", &["o", "O"]),
+    (Scope::Confirm, Action::Cancel, "cancel", &["c", "C", "Esc"]),
+];
+
+/// Keys bound to the actions of every scope.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(
+    try_from = "BTreeMap<String, Vec<String>>",
+    into = "BTreeMap<String, Vec<String>>"
+)]
+pub struct Keymap {
+    bindings: HashMap<(Scope, Action), Vec<KeyEvent>>,
+    actions: HashMap<(Scope, KeyEvent), Action>,
+}
+
+impl Keymap {
+    fn new(bindings: HashMap<(Scope, Action), Vec<KeyEvent>>) -> Result<Self, String> {
+        let mut actions = HashMap::new();
+        for ((scope, action), keys) in &bindings {
+            for key in keys {
+                actions.insert((*scope, *key), *action);
+            }
+        }
+        let keymap = Keymap { bindings, actions };
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// Returns the action bound to `key` in `scope`.
+    pub fn action(&self, scope: Scope, key: &KeyEvent) -> Option<Action> {
+        self.actions.get(&(scope, normalize(*key))).copied()
+    }
+
+    /// Returns the action bound to `key` in any of `scopes`, which must be
+    /// active at the same time.
+    pub fn action_in(&self, scopes: &[Scope], key: &KeyEvent) -> Option<Action> {
+        scopes.iter().find_map(|scope| self.action(*scope, key))
+    }
+
+    /// Returns the characters of `chars` bound to an action in any of
+    /// `scopes`. The actions take precedence over typing them as labels.
+    pub fn bound_chars<I: IntoIterator<Item = char>>(
+        &self,
+        scopes: &[Scope],
+        chars: I,
+    ) -> Vec<char> {
+        chars
+            .into_iter()
+            .filter(|c| {
+                let key = KeyEvent {
+                    code: KeyCode::Char(*c),
+                    modifiers: KeyModifiers::empty(),
+                };
+                self.action_in(scopes, &key).is_some()
+            })
+            .collect()
+    }
+
+    /// Returns the first key bound to an action, as shown in usage hints.
+    pub fn label(&self, scope: Scope, action: Action) -> String {
+        self.bindings
+            .get(&(scope, action))
+            .and_then(|keys| keys.first())
+            .map(format_key)
+            .unwrap_or_else(|| "-".to_owned())
+    }
+
+    fn check_conflicts(&self) -> Result<(), String> {
+        for scopes in ACTIVE_SCOPES {
+            let mut bound: HashMap<KeyEvent, String> = HashMap::new();
+            for (scope, action, name, _) in DEFAULT_BINDINGS {
+                if !scopes.contains(scope) {
+                    continue;
+                }
+                let full_name = format!("{}.{}", scope.name(), name);
+                for key in self.bindings.get(&(*scope, *action)).into_iter().flatten() {
+                    if let Some(other) = bound.insert(*key, full_name.clone()) {
+                        return Err(format!(
+                            "Key \"{}\" is bound to both {} and {}",
+                            format_key(key),
+                            other,
+                            full_name
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .map(|(scope, action, _, keys)| {
+                let keys = keys.iter().filter_map(|key| parse_key(key)).collect();
+                ((*scope, *action), keys)
+            })
+            .collect();
+        Keymap::new(bindings).expect("Conflicting default key bindings")
+    }
+}
+
+impl TryFrom<BTreeMap<String, Vec<String>>> for Keymap {
+    type Error = String;
+
+    fn try_from(config: BTreeMap<String, Vec<String>>) -> Result<Self, Self::Error> {
+        let mut bindings = Keymap::default().bindings;
+        for (full_name, keys) in config {
+            let (scope, action, _, _) = DEFAULT_BINDINGS
+                .iter()
+                .find(|(scope, _, name, _)| format!("{}.{}", scope.name(), name) == full_name)
+                .ok_or(format!("Unknown action in keymap: {}", full_name))?;
+            let keys = keys
+                .iter()
+                .map(|key| {
+                    parse_key(key).ok_or(format!("Invalid key \"{}\" for {}", key, full_name))
+                })
+                .collect::<Result<_, _>>()?;
+            bindings.insert((*scope, *action), keys);
+        }
+        Keymap::new(bindings)
+    }
+}
+
+impl From<Keymap> for BTreeMap<String, Vec<String>> {
+    fn from(keymap: Keymap) -> Self {
+        DEFAULT_BINDINGS
+            .iter()
+            .map(|(scope, action, name, _)| {
+                let keys = keymap
+                    .bindings
+                    .get(&(*scope, *action))
+                    .into_iter()
+                    .flatten()
+                    .map(format_key)
+                    .collect();
+                (format!("{}.{}", scope.name(), name), keys)
+            })
+            .collect()
+    }
+}
+
+/// Characters already tell whether Shift is held.
+fn normalize(key: KeyEvent) -> KeyEvent {
+    match key.code {
+        KeyCode::Char(_) => KeyEvent {
+            code: key.code,
+            modifiers: key.modifiers - KeyModifiers::SHIFT,
+        },
+        _ => key,
+    }
+}
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Space", KeyCode::Char(' ')),
+    ("Tab", KeyCode::Tab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+fn parse_key(text: &str) -> Option<KeyEvent> {
+    let mut rest = text;
+    let mut modifiers = KeyModifiers::empty();
+    loop {
+        let lowercase = rest.to_lowercase();
+        let (modifier, len) = if lowercase.starts_with("ctrl-") {
+            (KeyModifiers::CONTROL, 5)
+        } else if lowercase.starts_with("alt-") {
+            (KeyModifiers::ALT, 4)
+        } else if lowercase.starts_with("shift-") {
+            (KeyModifiers::SHIFT, 6)
+        } else if rest.starts_with('^') {
+            (KeyModifiers::CONTROL, 1)
+        } else {
+            break;
+        };
+        // The prefix may also be the key itself, as in `^` or `Alt--`
+        if rest.len() == len {
+            break;
+        }
+        modifiers |= modifier;
+        rest = &rest[len..];
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => KEY_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(rest))
+            .map(|(_, code)| *code)
+            .or_else(|| {
+                let number = rest.strip_prefix('F').or(rest.strip_prefix('f'))?;
+                number.parse().ok().map(KeyCode::F)
+            })?,
+    };
+    Some(normalize(KeyEvent { code, modifiers }))
+}
+
+fn format_key(key: &KeyEvent) -> String {
+    let mut text = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        text.push('^');
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        text.push_str("Alt-");
+    }
+    if key.modifiers.contains(KeyModifiers::SHIFT) {
+        text.push_str("Shift-");
+    }
+    match KEY_NAMES.iter().find(|(_, code)| *code == key.code) {
+        Some((name, _)) => text.push_str(name),
+        None => match key.code {
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::F(number) => text.push_str(&format!("F{}", number)),
+            _ => text.push('?'),
+        },
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent { code, modifiers }
+    }
+
+    #[test]
+    fn parse_keys() {
+        let ctrl_f = Some(key(KeyCode::Char('f'), KeyModifiers::CONTROL));
+        assert_eq!(parse_key("^f"), ctrl_f);
+        assert_eq!(parse_key("Ctrl-f"), ctrl_f);
+        assert_eq!(
+            parse_key("pageup"),
+            Some(key(KeyCode::PageUp, KeyModifiers::empty()))
+        );
+        assert_eq!(
+            parse_key("F12"),
+            Some(key(KeyCode::F(12), KeyModifiers::empty()))
+        );
+        assert_eq!(
+            parse_key("^"),
+            Some(key(KeyCode::Char('^'), KeyModifiers::empty()))
+        );
+        assert_eq!(
+            parse_key("Alt--"),
+            Some(key(KeyCode::Char('-'), KeyModifiers::ALT))
+        );
+        // Characters already tell whether Shift is held
+        assert_eq!(
+            parse_key("Shift-J"),
+            Some(key(KeyCode::Char('J'), KeyModifiers::empty()))
+        );
+        assert_eq!(parse_key("Nope"), None);
+        assert_eq!(parse_key(""), None);
+    }
+
+    #[test]
+    fn format_keys_round_trip() {
+        for text in &[
+            "a",
+            "J",
+            "^f",
+            "Alt-x",
+            "^Alt-Enter",
+            "Shift-Tab",
+            "Space",
+            "F5",
+            "^",
+        ] {
+            let key = parse_key(text).unwrap();
+            assert_eq!(format_key(&key), *text);
+            assert_eq!(parse_key(&format_key(&key)), Some(key));
+        }
+    }
+
+    #[test]
+    fn default_keys_round_trip() {
+        for (_, _, name, keys) in DEFAULT_BINDINGS {
+            for text in keys.iter() {
+                let key = parse_key(text).unwrap_or_else(|| panic!("Invalid key for {}", name));
+                assert_eq!(parse_key(&format_key(&key)), Some(key));
+            }
+        }
+    }
+
+    #[test]
+    fn config_rebinds_actions() {
+        let mut config = BTreeMap::new();
+        config.insert("exam.toggle_flag".to_owned(), vec!["b".to_owned()]);
+        let keymap = Keymap::try_from(config).unwrap();
+        assert_eq!(keymap.label(Scope::Exam, Action::ToggleFlag), "b");
+        assert_eq!(keymap.bound_chars(&[Scope::Exam], "abc".chars()), vec!['b']);
+    }
+
+    #[test]
+    fn config_rejects_conflicts() {
+        let mut config = BTreeMap::new();
+        config.insert("exam.toggle_flag".to_owned(), vec!["n".to_owned()]);
+        assert!(Keymap::try_from(config).is_err());
+    }
+}
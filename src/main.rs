@@ -5,8 +5,19 @@ extern crate bitflags;
 mod macros;
 
 mod app;
+mod cli;
 mod event;
+mod finder;
+mod highlight;
+mod keymap;
+mod markdown;
+mod mouse;
+mod preview;
 mod reducer;
+mod report;
+mod shuffle;
+mod srs;
+mod theme;
 mod toggle_buttons;
 mod ui;
 mod widget;
@@ -19,6 +30,7 @@ use event::Messages;
 use tui::widgets::{Block, Borders};
 
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -26,11 +38,81 @@ use tui::backend::CrosstermBackend;
 use tui::Terminal;
 
 use std::io::{stdout, Write};
+use std::process;
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let args = match cli::Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    if args.version {
+        println!("exhaust {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    let mut app = App::default();
+    app.load_config(args.config.as_deref())?;
+
+    if let Some(directory) = &args.directory {
+        if !directory.is_dir() {
+            eprintln!("Not a directory: {}", directory.display());
+            process::exit(2);
+        }
+        app.home.current_path = directory.canonicalize()?;
+    }
+    if args.autosave {
+        app.home.open_mode = OpenMode::AutoSave;
+    }
+    if args.shuffle {
+        app.home.shuffle = true;
+    }
+    if let Some(exam_path) = &args.exam {
+        let mut exam = match exam_path
+            .canonicalize()
+            .map_err(reducer::LoadError::Io)
+            .and_then(|exam_path| reducer::load_exam(&exam_path))
+        {
+            Ok(exam) => exam,
+            Err(err) => {
+                eprintln!("Unable to load {}: {}", exam_path.display(), err);
+                process::exit(1);
+            }
+        };
+        let exam_path = exam_path.canonicalize()?;
+        if args.seed.is_some() && args.seed != exam.shuffle_seed {
+            exam.shuffle_seed = args.seed;
+            exam.unsaved_changes = true;
+        } else if args.shuffle && exam.shuffle_seed.is_none() {
+            exam.shuffle_seed = Some(shuffle::new_seed());
+            exam.unsaved_changes = true;
+        }
+        exam.apply_shuffle();
+        if let Some(question) = args.question {
+            let number = question.min(exam.num_questions()).max(1);
+            if let Some(index) = exam.index_at_number(number) {
+                exam.display.question_index = index;
+            }
+        }
+        exam.display.time_limit = args.time_limit;
+        app.home.select_path(&exam_path);
+        app.home.exam_src = Some(exam_path);
+        app.exam = Some(exam);
+        app.route = AppRoute::DoExam;
+    }
+
     enable_raw_mode()?;
     let mut stdout = stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    if app.config.mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
@@ -38,9 +120,6 @@ fn main() -> Result<(), Box<dyn Error>> {
     let events = event::Events::new();
     terminal.hide_cursor()?;
 
-    let mut app = App::default();
-    app.load_config()?;
-
     terminal.clear()?;
 
     loop {
@@ -63,7 +142,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         let next_event = events.next()?;
         match next_event {
             Messages::Quit => {
+                // Do not cut off a save in progress
+                app.save_queue.wait();
                 disable_raw_mode()?;
+                if app.config.mouse {
+                    execute!(terminal.backend_mut(), DisableMouseCapture)?;
+                }
                 execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
                 terminal.show_cursor()?;
                 break;
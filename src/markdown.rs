@@ -0,0 +1,516 @@
+/*
+ * markdown.rs
+ *
+ * A lightweight Markdown renderer for the texts of items.
+ *
+ * Supports headings, lists, fenced code blocks, and bold, italic and code
+ * spans. Text is wrapped here rather than by `Paragraph::wrap`, so that list
+ * items keep their indentation and code blocks are not reflowed. Every line
+ * break of the source is kept. Code blocks are highlighted by the language
+ * after the opening fence, and long lines of code are only broken between
+ * tokens.
+ */
+use crate::highlight::{Highlighter, TokenKind};
+use crate::theme::SyntaxStyles;
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::Text;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const TAB_WIDTH: usize = 4;
+
+/// A piece of text in a single style.
+type Span = (String, Style);
+type Line = Vec<Span>;
+
+/// Renders `text` into lines of at most `width` columns, separated by `\n`.
+pub fn render(text: &str, width: u16, syntax: &SyntaxStyles) -> Vec<Text<'static>> {
+    to_texts(render_lines(text, width as usize, Style::default(), syntax))
+}
+
+/// Wraps `text` like `render`, without parsing it as Markdown.
+pub fn render_plain(text: &str, width: u16, style: Style) -> Vec<Text<'static>> {
+    let lines = text
+        .lines()
+        .flat_map(|line| wrap(vec![(line.to_owned(), style)], (width as usize).max(1), 0))
+        .collect();
+    to_texts(lines)
+}
+
+fn to_texts(lines: Vec<Line>) -> Vec<Text<'static>> {
+    let mut result = vec![];
+    lines.into_iter().for_each(|line| {
+        result.extend(
+            line.into_iter()
+                .map(|(text, style)| Text::styled(text, style)),
+        );
+        result.push(Text::raw("\n"));
+    });
+    result
+}
+
+/// Splits `line` into pieces, telling whether each piece matches `search`
+/// case-insensitively.
+pub fn split_matches<'a>(line: &'a str, search: &str) -> Vec<(&'a str, bool)> {
+    let lowercase = line.to_lowercase();
+    // Positions in the lowercase line only apply if lowercasing kept them
+    if search.is_empty() || lowercase.len() != line.len() {
+        return vec![(line, false)];
+    }
+
+    let mut pieces = vec![];
+    let mut start = 0;
+    for (pos, _) in lowercase.match_indices(search) {
+        if pos < start || !line.is_char_boundary(pos) || !line.is_char_boundary(pos + search.len())
+        {
+            continue;
+        }
+        pieces.push((&line[start..pos], false));
+        pieces.push((&line[pos..pos + search.len()], true));
+        start = pos + search.len();
+    }
+    pieces.push((&line[start..], false));
+    pieces
+}
+
+/// Restyles the matches of `search` in rendered texts. Matches running
+/// across differently styled pieces are not found.
+pub fn highlight<'a>(texts: Vec<Text<'a>>, search: &str, style: Style) -> Vec<Text<'a>> {
+    if search.is_empty() {
+        return texts;
+    }
+    texts
+        .into_iter()
+        .flat_map(|text| {
+            let (content, base) = match text {
+                Text::Raw(content) => (content, None),
+                Text::Styled(content, base) => (content, Some(base)),
+            };
+            split_matches(&content, search)
+                .into_iter()
+                .filter(|(piece, _)| !piece.is_empty())
+                .map(|(piece, matched)| match (matched, base) {
+                    (true, _) => Text::styled(piece.to_owned(), style),
+                    (false, Some(base)) => Text::styled(piece.to_owned(), base),
+                    (false, None) => Text::raw(piece.to_owned()),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Renders `text` into lines of at most `width` columns, on top of `style`.
+pub fn render_lines(text: &str, width: usize, style: Style, syntax: &SyntaxStyles) -> Vec<Line> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    let mut code_block: Option<Highlighter> = None;
+
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+        .lines()
+        .for_each(|line| {
+            let trimmed = line.trim_start();
+            let is_fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+
+            if is_fence {
+                code_block = match code_block {
+                    Some(_) => None,
+                    None => Some(Highlighter::new(trimmed.trim_start_matches(['`', '~']))),
+                };
+            } else if let Some(highlighter) = code_block.as_mut() {
+                // Wrapped lines of code are indented further than the line
+                let indent = line.len() - trimmed.len() + 2;
+                let is_known = highlighter.is_known();
+                let mut tokens = vec![];
+                for (text, kind) in highlighter.highlight_line(line) {
+                    let token_style = match is_known {
+                        true => token_style(kind, style, syntax),
+                        false => patch(style, syntax.code),
+                    };
+                    match kind {
+                        // Comments and strings may still be wrapped between words
+                        TokenKind::Comment | TokenKind::String => tokens.extend(
+                            split_words(&text)
+                                .into_iter()
+                                .map(|word| (word.to_owned(), token_style)),
+                        ),
+                        _ => tokens.push((text, token_style)),
+                    }
+                }
+                lines.extend(wrap_tokens(tokens, width, indent));
+            } else if let Some((level, heading)) = parse_heading(trimmed) {
+                let heading_style = match level {
+                    1 => style.modifier(style.modifier | Modifier::BOLD | Modifier::UNDERLINED),
+                    _ => style.modifier(style.modifier | Modifier::BOLD),
+                };
+                lines.extend(wrap(parse_inline(heading, heading_style, syntax), width, 0));
+            } else if let Some((marker, item)) = parse_list_item(line) {
+                let indent = marker.width();
+                let mut spans = vec![(marker, style)];
+                spans.extend(parse_inline(item, style, syntax));
+                lines.extend(wrap(spans, width, indent));
+            } else {
+                // Wrapped lines are aligned with the indentation of the line
+                let indent = line.len() - trimmed.len();
+                let mut spans = vec![(line[..indent].to_owned(), style)];
+                spans.extend(parse_inline(trimmed, style, syntax));
+                lines.extend(wrap(spans, width, indent));
+            }
+        });
+
+    lines
+}
+
+/// Applies the foreground colour and modifiers of `code` on top of `style`.
+fn patch(style: Style, code: Style) -> Style {
+    Style {
+        fg: match code.fg {
+            Color::Reset => style.fg,
+            fg => fg,
+        },
+        bg: style.bg,
+        modifier: style.modifier | code.modifier,
+    }
+}
+
+/// Styles a token of highlighted code on top of `style`.
+fn token_style(kind: TokenKind, style: Style, syntax: &SyntaxStyles) -> Style {
+    match kind {
+        TokenKind::Plain => style,
+        TokenKind::Keyword => patch(style, syntax.keyword),
+        TokenKind::Literal | TokenKind::Number => patch(style, syntax.literal),
+        TokenKind::String => patch(style, syntax.string),
+        TokenKind::Comment => patch(style, syntax.comment),
+        TokenKind::Type => patch(style, syntax.type_name),
+    }
+}
+
+/// Parses `# Heading` into its level and text.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    match line[level..].chars().next() {
+        None => Some((level, "")),
+        Some(' ') => Some((level, line[level..].trim())),
+        Some(_) => None,
+    }
+}
+
+/// Parses `- item` or `1. item` into the marker to display and the text.
+fn parse_list_item(line: &str) -> Option<(String, &str)> {
+    let trimmed = line.trim_start();
+    let indent = " ".repeat(line.len() - trimmed.len());
+
+    for bullet in &["- ", "* ", "+ "] {
+        if trimmed.starts_with(bullet) {
+            return Some((format!("{}• ", indent), &trimmed[2..]));
+        }
+    }
+
+    let num_digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    let rest = &trimmed[num_digits..];
+    if num_digits > 0 && num_digits < 10 && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        let number = &trimmed[..num_digits];
+        return Some((format!("{}{}. ", indent, number), &rest[2..]));
+    }
+    None
+}
+
+/// Parses bold, italic and code spans.
+fn parse_inline(text: &str, style: Style, syntax: &SyntaxStyles) -> Line {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = vec![];
+    let mut buffer = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Escaped punctuation
+        if c == '\\' && i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() {
+            buffer.push(chars[i + 1]);
+            i += 2;
+            continue;
+        }
+
+        // Code spans end with as many backticks as they start with
+        if c == '`' {
+            let run = count_run(&chars, i, '`');
+            if let Some(close) = find_code_close(&chars, i + run, run) {
+                flush(&mut buffer, &mut spans, style);
+                let code: String = chars[i + run..close].iter().collect();
+                spans.push((code, patch(style, syntax.code)));
+                i = close + run;
+                continue;
+            }
+            buffer.extend(&chars[i..i + run]);
+            i += run;
+            continue;
+        }
+
+        // `**bold**` and `*italic*`, or the same with underscores
+        if c == '*' || c == '_' {
+            let run = count_run(&chars, i, c).min(2);
+            if can_open(&chars, i, run) {
+                if let Some(close) = find_emphasis_close(&chars, i + run, c, run) {
+                    flush(&mut buffer, &mut spans, style);
+                    let inner: String = chars[i + run..close].iter().collect();
+                    let modifier = match run {
+                        2 => Modifier::BOLD,
+                        _ => Modifier::ITALIC,
+                    };
+                    spans.extend(parse_inline(
+                        &inner,
+                        style.modifier(style.modifier | modifier),
+                        syntax,
+                    ));
+                    i = close + run;
+                    continue;
+                }
+            }
+            buffer.extend(&chars[i..i + run]);
+            i += run;
+            continue;
+        }
+
+        buffer.push(c);
+        i += 1;
+    }
+
+    flush(&mut buffer, &mut spans, style);
+    spans
+}
+
+fn flush(buffer: &mut String, spans: &mut Line, style: Style) {
+    if !buffer.is_empty() {
+        spans.push((buffer.clone(), style));
+        buffer.clear();
+    }
+}
+
+fn count_run(chars: &[char], start: usize, c: char) -> usize {
+    chars[start..].iter().take_while(|ch| **ch == c).count()
+}
+
+fn find_code_close(chars: &[char], start: usize, run: usize) -> Option<usize> {
+    let mut j = start;
+    while j < chars.len() {
+        if chars[j] == '`' {
+            let close_run = count_run(chars, j, '`');
+            if close_run == run {
+                return Some(j);
+            }
+            j += close_run;
+        } else {
+            j += 1;
+        }
+    }
+    None
+}
+
+/// Emphasis opens before non-whitespace. Underscores must also start a word,
+/// so that `snake_case` is left as is.
+fn can_open(chars: &[char], start: usize, run: usize) -> bool {
+    let next = match chars.get(start + run) {
+        Some(next) => *next,
+        None => return false,
+    };
+    let prev_is_alphanumeric = start > 0 && chars[start - 1].is_alphanumeric();
+    !(next.is_whitespace() || chars[start] == '_' && prev_is_alphanumeric)
+}
+
+fn find_emphasis_close(chars: &[char], start: usize, c: char, run: usize) -> Option<usize> {
+    let mut j = start;
+    while j < chars.len() {
+        if chars[j] != c {
+            j += 1;
+            continue;
+        }
+        let close_run = count_run(chars, j, c);
+        let prev_is_whitespace = chars[j - 1].is_whitespace();
+        let next_is_alphanumeric = chars
+            .get(j + close_run)
+            .map(|next| next.is_alphanumeric())
+            .unwrap_or(false);
+        if j > start
+            && close_run == run
+            && !prev_is_whitespace
+            && !(c == '_' && next_is_alphanumeric)
+        {
+            return Some(j);
+        }
+        j += close_run;
+    }
+    None
+}
+
+/// Wraps spans at whitespace into lines of at most `width` columns. Wrapped
+/// lines start with `indent` spaces.
+fn wrap(spans: Line, width: usize, indent: usize) -> Vec<Line> {
+    let words = spans
+        .iter()
+        .flat_map(|(text, style)| {
+            split_words(text)
+                .into_iter()
+                .map(move |word| (word.to_owned(), *style))
+        })
+        .collect();
+    wrap_tokens(words, width, indent)
+}
+
+/// Wraps tokens into lines like `wrap`, breaking lines only between tokens
+/// unless a token is longer than the line.
+fn wrap_tokens(tokens: Line, width: usize, indent: usize) -> Vec<Line> {
+    let indent = if indent * 2 > width { 0 } else { indent };
+    let mut lines = vec![];
+    let mut line: Line = vec![];
+    let mut line_width = 0;
+
+    for (word, style) in tokens {
+        let word_width = word.width();
+        let is_whitespace = word.trim().is_empty();
+
+        if line_width + word_width > width && line_width > indent {
+            lines.push(line);
+            line = vec![(" ".repeat(indent), style)];
+            line_width = indent;
+            // Whitespace at the break is dropped
+            if is_whitespace {
+                continue;
+            }
+        }
+
+        if line_width + word_width <= width {
+            line_width += word_width;
+            line.push((word, style));
+            continue;
+        }
+
+        // Words longer than the line are broken anywhere
+        let mut piece = String::new();
+        for c in word.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if line_width + char_width > width && line_width > indent {
+                line.push((piece.clone(), style));
+                lines.push(line);
+                line = vec![(" ".repeat(indent), style)];
+                line_width = indent;
+                piece.clear();
+            }
+            piece.push(c);
+            line_width += char_width;
+        }
+        line.push((piece, style));
+    }
+
+    lines.push(line);
+    lines
+}
+
+/// Splits text into words and the whitespace between them.
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut start = 0;
+    let mut prev_is_whitespace = None;
+    for (i, c) in text.char_indices() {
+        let is_whitespace = c.is_whitespace();
+        if prev_is_whitespace.is_some_and(|prev| prev != is_whitespace) {
+            words.push(&text[start..i]);
+            start = i;
+        }
+        prev_is_whitespace = Some(is_whitespace);
+    }
+    if start < text.len() {
+        words.push(&text[start..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders `text` into the contents of its lines, without trailing
+    /// whitespace.
+    fn plain_lines(text: &str, width: usize) -> Vec<String> {
+        let syntax = SyntaxStyles {
+            code: Style::default(),
+            keyword: Style::default(),
+            literal: Style::default(),
+            string: Style::default(),
+            comment: Style::default(),
+            type_name: Style::default(),
+        };
+        render_lines(text, width, Style::default(), &syntax)
+            .into_iter()
+            .map(|line| {
+                let text: String = line.into_iter().map(|(text, _)| text).collect();
+                text.trim_end().to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wrap_at_whitespace() {
+        assert_eq!(
+            plain_lines("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(
+            plain_lines("first\n\nthird", 10),
+            vec!["first", "", "third"]
+        );
+    }
+
+    #[test]
+    fn wrap_long_words_anywhere() {
+        assert_eq!(plain_lines("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn wrapped_list_items_keep_their_indentation() {
+        assert_eq!(
+            plain_lines("- one two three", 10),
+            vec!["• one two", "  three"]
+        );
+        assert_eq!(plain_lines("2) **bold** item", 20), vec!["2. bold item"]);
+    }
+
+    #[test]
+    fn code_blocks_are_not_reflowed() {
+        assert_eq!(
+            plain_lines("```\nlet  x = 1;\n```", 20),
+            vec!["let  x = 1;"]
+        );
+    }
+
+    #[test]
+    fn split_matches_ignoring_case() {
+        assert_eq!(
+            split_matches("Rust and rust", "rust"),
+            vec![
+                ("", false),
+                ("Rust", true),
+                (" and ", false),
+                ("rust", true),
+                ("", false)
+            ]
+        );
+        assert_eq!(split_matches("none", "x"), vec![("none", false)]);
+        assert_eq!(split_matches("any", ""), vec![("any", false)]);
+    }
+
+    #[test]
+    fn split_matches_do_not_overlap() {
+        assert_eq!(
+            split_matches("aaa", "aa"),
+            vec![("", false), ("aa", true), ("a", false)]
+        );
+    }
+
+    #[test]
+    fn split_matches_keep_lines_changing_length() {
+        // Lowercasing `İ` adds a byte, so positions would not line up
+        assert_eq!(split_matches("İstanbul", "stan"), vec![("İstanbul", false)]);
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * mouse.rs
+ *
+ * Hit-testing of mouse events against the areas drawn in the last frame.
+ *
+ * Widgets register the `Rect`s of their clickable parts while drawing, and
+ * look up the target under the mouse when propagating a `Messages::Mouse`.
+ * The areas are cleared at the start of every frame, and later areas are on
+ * top of earlier ones, so modals drawn over the exam receive the clicks.
+ */
+use crate::keymap::Action;
+use crossterm::event::{MouseButton, MouseEvent};
+use std::cell::RefCell;
+use tui::layout::Rect;
+
+/// A clickable part of the screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MouseTarget {
+    /// A modal button, clicked as the action of its key
+    Button(Action),
+    /// The list of paths on the home screen
+    HomeList,
+    /// The scrollable question and answer of an item
+    Question,
+    /// A selection row, by its position on screen
+    Selection(usize),
+    /// A number in the items grid, by the index of its item
+    Item(usize),
+    /// A row in the list of search results, by its position
+    SearchResult(usize),
+    /// A row in the assets modal, by the index of its asset
+    Asset(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MouseAction {
+    Click(MouseTarget),
+    ScrollUp(MouseTarget),
+    ScrollDown(MouseTarget),
+}
+
+/// The targets drawn in the last frame. Drawing only borrows the app, so the
+/// areas are kept in a `RefCell`.
+#[derive(Default)]
+pub struct MouseAreas {
+    areas: RefCell<Vec<(Rect, MouseTarget)>>,
+}
+
+impl MouseAreas {
+    pub fn clear(&self) {
+        self.areas.borrow_mut().clear();
+    }
+
+    pub fn add(&self, area: Rect, target: MouseTarget) {
+        self.areas.borrow_mut().push((area, target));
+    }
+
+    /// Returns the target drawn last at the given cell.
+    pub fn target_at(&self, column: u16, row: u16) -> Option<MouseTarget> {
+        self.areas
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(area, _)| {
+                area.left() <= column
+                    && column < area.right()
+                    && area.top() <= row
+                    && row < area.bottom()
+            })
+            .map(|(_, target)| *target)
+    }
+
+    /// Maps left clicks and the wheel to the target under the mouse.
+    pub fn action(&self, event: &MouseEvent) -> Option<MouseAction> {
+        match *event {
+            MouseEvent::Down(MouseButton::Left, column, row, _) => {
+                self.target_at(column, row).map(MouseAction::Click)
+            }
+            MouseEvent::ScrollUp(column, row, _) => {
+                self.target_at(column, row).map(MouseAction::ScrollUp)
+            }
+            MouseEvent::ScrollDown(column, row, _) => {
+                self.target_at(column, row).map(MouseAction::ScrollDown)
+            }
+            _ => None,
+        }
+    }
+}
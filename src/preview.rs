@@ -0,0 +1,243 @@
+/*
+ * preview.rs
+ *
+ * In-terminal preview of image assets.
+ *
+ * PNG and JPEG files are decoded and drawn with upper half blocks, so that
+ * each cell shows two pixels: the top one in the foreground colour and the
+ * bottom one in the background colour. Transparent pixels are blended onto
+ * white, as diagrams are usually drawn on paper. Terminals without truecolor
+ * get the nearest indexed colours.
+ */
+use crate::theme;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Color;
+use tui::widgets::{Block, Widget};
+
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G'];
+const JPEG_SIGNATURE: &[u8] = &[0xff, 0xd8, 0xff];
+const UPPER_HALF_BLOCK: &str = "▀";
+
+/// A decoded image in 8-bit RGB.
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+// The pixels are left out of debug output
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl Image {
+    /// Decodes the PNG or JPEG file at `path`.
+    pub fn load(path: &Path) -> Result<Image, String> {
+        let mut signature = [0u8; 4];
+        File::open(path)
+            .and_then(|mut file| file.read_exact(&mut signature))
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))?;
+
+        let file = File::open(path).map_err(|err| err.to_string())?;
+        let image = if signature.starts_with(PNG_SIGNATURE) {
+            decode_png(BufReader::new(file))
+        } else if signature.starts_with(JPEG_SIGNATURE) {
+            decode_jpeg(BufReader::new(file))
+        } else {
+            return Err("Only PNG and JPEG images can be previewed".to_owned());
+        };
+
+        image
+            .and_then(|image| match image.width > 0 && image.height > 0 {
+                true => Ok(image),
+                false => Err("Empty image".to_owned()),
+            })
+            .map_err(|err| format!("Unable to decode {}: {}", path.display(), err))
+    }
+
+    /// Averages the pixels within the given bounds.
+    fn average(&self, x0: usize, x1: usize, y0: usize, y1: usize) -> [u8; 3] {
+        let mut sum = [0usize; 3];
+        for y in y0..y1 {
+            for pixel in &self.pixels[y * self.width + x0..y * self.width + x1] {
+                (0..3).for_each(|channel| sum[channel] += pixel[channel] as usize);
+            }
+        }
+        let count = (x1 - x0) * (y1 - y0);
+        [
+            (sum[0] / count) as u8,
+            (sum[1] / count) as u8,
+            (sum[2] / count) as u8,
+        ]
+    }
+}
+
+/// Returns whether the file at `path` looks like an image by its extension.
+pub fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| {
+            let extension = extension.to_lowercase();
+            ["png", "jpg", "jpeg"].contains(&extension.as_str())
+        })
+        .unwrap_or(false)
+}
+
+fn decode_png<R: Read>(reader: R) -> Result<Image, String> {
+    let mut decoder = png::Decoder::new(reader);
+    // Palettes and low bit depths are expanded to 8 bits per sample
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let (info, mut reader) = decoder.read_info().map_err(|err| err.to_string())?;
+    let mut buffer = vec![0; info.buffer_size()];
+    reader
+        .next_frame(&mut buffer)
+        .map_err(|err| err.to_string())?;
+
+    let samples = info.color_type.samples();
+    let pixels = buffer
+        .chunks(info.line_size)
+        .take(info.height as usize)
+        .flat_map(|line| line.chunks(samples).take(info.width as usize))
+        .map(|pixel| match info.color_type {
+            png::ColorType::Grayscale => [pixel[0]; 3],
+            png::ColorType::GrayscaleAlpha => blend([pixel[0]; 3], pixel[1]),
+            png::ColorType::RGBA => blend([pixel[0], pixel[1], pixel[2]], pixel[3]),
+            _ => [pixel[0], pixel[1], pixel[2]],
+        })
+        .collect();
+
+    Ok(Image {
+        width: info.width as usize,
+        height: info.height as usize,
+        pixels,
+    })
+}
+
+fn decode_jpeg<R: Read>(reader: R) -> Result<Image, String> {
+    let mut decoder = jpeg_decoder::Decoder::new(reader);
+    let buffer = decoder.decode().map_err(|err| err.to_string())?;
+    let info = decoder.info().ok_or("Missing image information")?;
+
+    let pixels = match info.pixel_format {
+        jpeg_decoder::PixelFormat::L8 => buffer.iter().map(|value| [*value; 3]).collect(),
+        jpeg_decoder::PixelFormat::RGB24 => buffer
+            .chunks(3)
+            .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect(),
+        jpeg_decoder::PixelFormat::CMYK32 => buffer
+            .chunks(4)
+            .map(|pixel| {
+                let k = 255 - pixel[3] as usize;
+                let channel = |value: u8| ((255 - value as usize) * k / 255) as u8;
+                [channel(pixel[0]), channel(pixel[1]), channel(pixel[2])]
+            })
+            .collect(),
+    };
+
+    Ok(Image {
+        width: info.width as usize,
+        height: info.height as usize,
+        pixels,
+    })
+}
+
+/// Blends a pixel with the given opacity onto white.
+fn blend(pixel: [u8; 3], alpha: u8) -> [u8; 3] {
+    let channel = |value: u8| {
+        let alpha = alpha as usize;
+        ((value as usize * alpha + 255 * (255 - alpha)) / 255) as u8
+    };
+    [channel(pixel[0]), channel(pixel[1]), channel(pixel[2])]
+}
+
+/// Draws an image scaled down to fit the area, keeping its aspect ratio.
+pub struct ImagePreview<'a> {
+    block: Option<Block<'a>>,
+    image: &'a Image,
+    /// Draw in indexed colours otherwise
+    truecolor: bool,
+}
+
+impl<'a> ImagePreview<'a> {
+    pub fn new(image: &'a Image) -> ImagePreview<'a> {
+        ImagePreview {
+            block: None,
+            image,
+            truecolor: true,
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> ImagePreview<'a> {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn truecolor(mut self, truecolor: bool) -> ImagePreview<'a> {
+        self.truecolor = truecolor;
+        self
+    }
+}
+
+impl<'a> Widget for ImagePreview<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block {
+            Some(ref mut b) => {
+                b.render(area, buf);
+                b.inner(area)
+            }
+            None => area,
+        };
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        // Every cell holds two pixels stacked vertically. Images are never
+        // scaled up, to keep small icons sharp.
+        let image = self.image;
+        let max_width = area.width as usize;
+        let max_height = area.height as usize * 2;
+        let scale = (image.width as f64 / max_width as f64)
+            .max(image.height as f64 / max_height as f64)
+            .max(1f64);
+        let width = ((image.width as f64 / scale) as usize).max(1);
+        let height = ((image.height as f64 / scale) as usize).max(1);
+
+        // Center the image in the area
+        let left = area.left() + (max_width - width) as u16 / 2;
+        let top = area.top() + ((max_height - height) / 2 / 2) as u16;
+
+        let pixel_at = |x: usize, y: usize| {
+            let x0 = x * image.width / width;
+            let x1 = ((x + 1) * image.width / width).max(x0 + 1);
+            let y0 = y * image.height / height;
+            let y1 = ((y + 1) * image.height / height).max(y0 + 1);
+            let [r, g, b] = image.average(x0, x1, y0, y1);
+            match self.truecolor {
+                true => Color::Rgb(r, g, b),
+                false => theme::to_indexed(Color::Rgb(r, g, b)),
+            }
+        };
+
+        for row in 0..height.div_ceil(2) {
+            for x in 0..width {
+                let cell = buf.get_mut(left + x as u16, top + row as u16);
+                cell.set_symbol(UPPER_HALF_BLOCK)
+                    .set_fg(pixel_at(x, row * 2));
+                // The last row of an image with an odd height is only half filled
+                if row * 2 + 1 < height {
+                    cell.set_bg(pixel_at(x, row * 2 + 1));
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,16 @@
 use crate::app::*;
 use crate::event::*;
+use crate::finder::{self, Finder};
+use crate::preview::{self, Image};
+use crate::report::Report;
+use crate::shuffle;
+use crate::srs;
 use libflate::gzip::{Decoder, Encoder};
-use std::fs::File;
-use std::io::Read;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::mpsc;
 use std::thread;
@@ -20,11 +28,29 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
                 }
             };
 
+            // Starts from the first wrong item in the results
+            if let AppRoute::Results = &route {
+                let has_missed_items = state
+                    .exam
+                    .as_ref()
+                    .map(|exam| !Report::new(exam).missed_items.is_empty())
+                    .unwrap_or(false);
+                state.results.list_state.select(match has_missed_items {
+                    true => Some(0),
+                    false => None,
+                });
+                state.results.status = None;
+            }
+
             state.route = route;
             None
         }
         Messages::ToggleSelection(sel) => match &state.route {
             AppRoute::DoExam => {
+                if state.exam.as_ref()?.is_submitted() {
+                    return None;
+                }
+
                 let exam = &state.exam.as_ref().unwrap();
                 let index = exam.display.question_index;
                 let question = state.exam.as_mut().unwrap().question_at_mut(index)?;
@@ -65,27 +91,343 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
             }
             _ => Some(event),
         },
+        Messages::FlipCard => {
+            if let Some(exam) = state.exam.as_mut() {
+                exam.display.card_flipped = !exam.display.card_flipped;
+            }
+            None
+        }
+        Messages::GradeCard(grade) => match &state.route {
+            AppRoute::DoExam => {
+                let exam = &state.exam.as_ref().unwrap();
+                let index = exam.display.question_index;
+                let item = state.exam.as_mut().unwrap().question_at_mut(index)?;
+                let modified = match item {
+                    Item::Card(card) => {
+                        if card.user_grade == grade {
+                            false
+                        } else {
+                            card.user_grade = grade;
+                            true
+                        }
+                    }
+                    _ => false,
+                };
+
+                if modified {
+                    if let Some(exam) = state.exam.as_mut() {
+                        exam.unsaved_changes = true;
+
+                        // Cards are reviewed once self-graded
+                        if exam.display.studying {
+                            let scoring = exam.scoring;
+                            if let Some(item) = exam.question_at_mut(index) {
+                                item.record_review(scoring, srs::today());
+                            }
+                        }
+                    }
+                    if let OpenMode::AutoSave = &state.home.open_mode {
+                        save_state(state, tx.clone());
+                    }
+                }
+
+                None
+            }
+            _ => Some(event),
+        },
+        Messages::ToggleFlag => match &state.route {
+            AppRoute::DoExam => {
+                if let Some(exam) = state.exam.as_mut() {
+                    let index = exam.display.question_index;
+                    if let Some(item) = exam.question_at_mut(index) {
+                        item.toggle_flag();
+                    }
+                    exam.unsaved_changes = true;
+                }
+                if let OpenMode::AutoSave = &state.home.open_mode {
+                    save_state(state, tx.clone());
+                }
+                None
+            }
+            _ => Some(event),
+        },
+        Messages::UpdateAnswerInput(evt) => {
+            let exam = state.exam.as_mut()?;
+            // Answers can no longer be edited once submitted
+            if exam.is_submitted() {
+                exam.display.answer_input = None;
+                return None;
+            }
+
+            let index = exam.display.question_index;
+            match evt {
+                UpdateAnswerInputEvent::Open => {
+                    let user_answer = exam.question_at(index)?.get_user_answer()?;
+                    exam.display.answer_input = Some(user_answer.to_owned());
+                }
+                UpdateAnswerInputEvent::Push(c) => {
+                    exam.display.answer_input.as_mut()?.push(c);
+                }
+                UpdateAnswerInputEvent::Pop => {
+                    exam.display.answer_input.as_mut()?.pop();
+                }
+                UpdateAnswerInputEvent::Cancel => {
+                    exam.display.answer_input = None;
+                }
+                UpdateAnswerInputEvent::Submit => {
+                    let answer = exam.display.answer_input.take()?.trim().to_owned();
+                    let modified = match exam.question_at_mut(index)? {
+                        Item::Text(question) if question.user_answer != answer => {
+                            question.user_answer = answer;
+                            true
+                        }
+                        Item::Numeric(question) if question.user_answer != answer => {
+                            question.user_answer = answer;
+                            true
+                        }
+                        _ => false,
+                    };
+
+                    if modified {
+                        exam.unsaved_changes = true;
+                        if let OpenMode::AutoSave = &state.home.open_mode {
+                            save_state(state, tx.clone());
+                        }
+                    }
+                }
+            };
+            None
+        }
+        Messages::ExamSearch(action) => {
+            let exam = state.exam.as_mut()?;
+            let display = &mut exam.display;
+            match action {
+                ExamSearchActions::Search(evt) => match evt {
+                    UpdateSearchEvent::Open => display.search_input = Some(String::new()),
+                    UpdateSearchEvent::Push(c) => {
+                        display.search_input.as_mut()?.push(c);
+                    }
+                    UpdateSearchEvent::Pop => {
+                        display.search_input.as_mut()?.pop();
+                    }
+                    UpdateSearchEvent::Cancel => display.search_input = None,
+                    UpdateSearchEvent::Submit => {
+                        let query = display.search_input.take()?.to_lowercase();
+                        if query.is_empty() {
+                            return None;
+                        }
+                        let results = exam.search(&query);
+                        // Starts from the current item, in display order
+                        let visible_indices = exam.visible_indices();
+                        let pos_of =
+                            |index: usize| visible_indices.iter().position(|i| *i == index);
+                        let current_pos = pos_of(exam.display.question_index).unwrap_or(0);
+                        let current = results
+                            .iter()
+                            .position(|index| pos_of(*index).is_some_and(|pos| pos >= current_pos))
+                            .unwrap_or(0);
+                        exam.display.search = Some(ExamSearch {
+                            query,
+                            results,
+                            current,
+                        });
+                    }
+                },
+                ExamSearchActions::NextMatch => {
+                    let search = display.search.as_mut()?;
+                    search.current = (search.current + 1) % search.results.len().max(1);
+                }
+                ExamSearchActions::PrevMatch => {
+                    let search = display.search.as_mut()?;
+                    let len = search.results.len().max(1);
+                    search.current = (search.current + len - 1) % len;
+                }
+                ExamSearchActions::Select(pos) => {
+                    let search = display.search.as_mut()?;
+                    search.current = pos.min(search.results.len().saturating_sub(1));
+                }
+                ExamSearchActions::Close => {
+                    display.search = None;
+                    return None;
+                }
+            };
+
+            // Shows the current result
+            let search = exam.display.search.as_ref()?;
+            let index = *search.results.get(search.current)?;
+            if index != exam.display.question_index {
+                tx.send(Messages::UpdateQuestionIndex(
+                    UpdateQuestionIndexEvent::Set(index),
+                ))
+                .unwrap();
+            }
+            None
+        }
+        Messages::SetTrueFalse(value) => {
+            let exam = state.exam.as_mut()?;
+            if exam.is_submitted() {
+                return None;
+            }
+
+            let index = exam.display.question_index;
+            let modified = match exam.question_at_mut(index)? {
+                Item::TrueFalse(question) if question.user_answer != Some(value) => {
+                    question.user_answer = Some(value);
+                    true
+                }
+                _ => false,
+            };
+
+            if modified {
+                exam.unsaved_changes = true;
+                if let OpenMode::AutoSave = &state.home.open_mode {
+                    save_state(state, tx.clone());
+                }
+            }
+            None
+        }
+        Messages::UpdateStepOrder(evt) => {
+            let exam = state.exam.as_mut()?;
+            let index = exam.display.question_index;
+            let mut order = exam.step_order(index);
+            let num_steps = order.len();
+            let cursor = exam.display.entry_cursor.min(num_steps.checked_sub(1)?);
+
+            // The cursor can be moved after submitted, to read the steps
+            let next_cursor = match evt {
+                UpdateStepOrderEvent::Next => (cursor + 1).min(num_steps - 1),
+                UpdateStepOrderEvent::Prev => cursor.saturating_sub(1),
+                _ if exam.is_submitted() => return None,
+                UpdateStepOrderEvent::MoveDown if cursor + 1 < num_steps => {
+                    order.swap(cursor, cursor + 1);
+                    cursor + 1
+                }
+                UpdateStepOrderEvent::MoveUp if cursor > 0 => {
+                    order.swap(cursor, cursor - 1);
+                    cursor - 1
+                }
+                UpdateStepOrderEvent::MoveDown
+                | UpdateStepOrderEvent::MoveUp
+                | UpdateStepOrderEvent::Submit => cursor,
+            };
+            exam.display.entry_cursor = next_cursor;
+
+            // Moving a step also answers the question
+            let modified = match (&evt, exam.question_at_mut(index)?) {
+                (UpdateStepOrderEvent::Next, _) | (UpdateStepOrderEvent::Prev, _) => false,
+                (_, Item::Ordering(question)) if question.user_order != order => {
+                    question.user_order = order;
+                    true
+                }
+                _ => false,
+            };
+
+            if modified {
+                exam.unsaved_changes = true;
+                if let OpenMode::AutoSave = &state.home.open_mode {
+                    save_state(state, tx.clone());
+                }
+            }
+            None
+        }
+        Messages::UpdateMatching(evt) => {
+            let exam = state.exam.as_mut()?;
+            let index = exam.display.question_index;
+            let is_submitted = exam.is_submitted();
+            let cursor = exam.display.entry_cursor;
+            let question = match exam.question_at_mut(index)? {
+                Item::Matching(question) => question,
+                _ => return None,
+            };
+            let num_pairs = question.num_pairs();
+            let cursor = cursor.min(num_pairs.checked_sub(1)?);
+
+            let mut next_cursor = cursor;
+            let modified = match evt {
+                UpdateMatchingEvent::Next => {
+                    next_cursor = (cursor + 1).min(num_pairs - 1);
+                    false
+                }
+                UpdateMatchingEvent::Prev => {
+                    next_cursor = cursor.saturating_sub(1);
+                    false
+                }
+                _ if is_submitted => false,
+                UpdateMatchingEvent::Assign(right) if right < num_pairs => {
+                    question.user_matches.resize(num_pairs, None);
+                    // Each right entry pairs with one left entry only
+                    question.user_matches.iter_mut().for_each(|user_match| {
+                        if *user_match == Some(right) {
+                            *user_match = None;
+                        }
+                    });
+                    question.user_matches[cursor] = Some(right);
+                    // Continues with the next entry
+                    next_cursor = (cursor + 1).min(num_pairs - 1);
+                    true
+                }
+                UpdateMatchingEvent::Assign(_) => false,
+                UpdateMatchingEvent::Clear => match question.user_match(cursor) {
+                    Some(_) => {
+                        question.user_matches[cursor] = None;
+                        true
+                    }
+                    None => false,
+                },
+            };
+            exam.display.entry_cursor = next_cursor;
+
+            if modified {
+                exam.unsaved_changes = true;
+                if let OpenMode::AutoSave = &state.home.open_mode {
+                    save_state(state, tx.clone());
+                }
+            }
+            None
+        }
         Messages::UpdateQuestionIndex(evt) => match &state.route {
             AppRoute::DoExam => {
                 let exam = state.exam.as_ref().unwrap();
                 let question_index = exam.display.question_index;
-                let max_index = exam.num_questions() - 1;
+                // Navigates in the display order, skipping items not under review
+                let visible_indices = exam.visible_indices();
+                let num_visible = visible_indices.len();
+                let pos = visible_indices
+                    .iter()
+                    .position(|index| *index == question_index);
                 let next_index = match &evt {
                     UpdateQuestionIndexEvent::Next => {
-                        if question_index < max_index {
-                            question_index + 1
-                        } else {
-                            0
-                        }
+                        let next_pos = pos.map(|pos| (pos + 1) % num_visible).unwrap_or(0);
+                        *visible_indices.get(next_pos)?
                     }
                     UpdateQuestionIndexEvent::Prev => {
-                        if question_index > 0 {
-                            question_index - 1
-                        } else {
-                            max_index
-                        }
+                        let prev_pos = pos
+                            .map(|pos| (pos + num_visible - 1) % num_visible)
+                            .unwrap_or(0);
+                        *visible_indices.get(prev_pos)?
+                    }
+                    UpdateQuestionIndexEvent::NextFlagged => {
+                        let start = pos.map(|pos| pos + 1).unwrap_or(0);
+                        (0..num_visible)
+                            .map(|offset| visible_indices[(start + offset) % num_visible])
+                            .find(|index| {
+                                exam.question_at(*index)
+                                    .map(|item| item.is_flagged())
+                                    .unwrap_or(false)
+                            })?
+                    }
+                    UpdateQuestionIndexEvent::PrevFlagged => {
+                        let start = pos.unwrap_or(0) + num_visible;
+                        (1..=num_visible)
+                            .map(|offset| visible_indices[(start - offset) % num_visible])
+                            .find(|index| {
+                                exam.question_at(*index)
+                                    .map(|item| item.is_flagged())
+                                    .unwrap_or(false)
+                            })?
                     }
                     UpdateQuestionIndexEvent::Set(index) => {
+                        let max_index = exam.num_questions().checked_sub(1)?;
                         if *index > max_index {
                             max_index
                         } else {
@@ -93,12 +435,26 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
                         }
                     }
                 };
+                // Jumping to an item not under review leaves the review
+                let leave_review = !visible_indices.contains(&next_index);
                 state.exam.as_mut().map(move |exam| {
+                    if leave_review {
+                        exam.display.review_items = None;
+                        exam.display.studying = false;
+                    }
+
                     // Update question index
                     exam.display.question_index = next_index;
 
                     // Reset scroll position
                     exam.display.question_scroll_pos = 0;
+
+                    // Show the front side of the next card
+                    exam.display.card_flipped = false;
+
+                    // Discard the answer being typed
+                    exam.display.answer_input = None;
+                    exam.display.entry_cursor = 0;
                 });
                 None
             }
@@ -110,45 +466,232 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
             });
             None
         }
-        Messages::ToggleExamResult => {
-            state.exam.as_mut().map(|exam| {
-                exam.display.display_answer = !exam.display.display_answer;
+        Messages::Tick => {
+            if let (AppRoute::DoExam, Some(exam)) = (&state.route, state.exam.as_mut()) {
+                if exam.time_limit().is_none() || exam.is_submitted() {
+                    return None;
+                }
+                exam.elapsed_time += TICK_RATE.as_secs();
+
+                // Submits the exam when the time is up
+                if exam.is_time_up() {
+                    exam.submit();
+                    exam.display.display_answer = true;
+                    exam.display.answer_input = None;
+                }
+            }
+            None
+        }
+        Messages::ToggleReview => {
+            let exam = state.exam.as_mut()?;
+            let scoring = exam.scoring;
+            toggle_review(exam, |item| {
+                matches!(
+                    item.get_result(scoring),
+                    QuestionResult::Wrong | QuestionResult::Partial
+                )
             });
             None
         }
-        Messages::UpdateHomeSelected(evt) => {
-            let paths = state.home.get_paths().unwrap();
-            let max_index = if paths.len() > 0 {
-                paths.len() - 1
-            } else {
+        Messages::ToggleFlaggedReview => {
+            toggle_review(state.exam.as_mut()?, Item::is_flagged);
+            None
+        }
+        Messages::ToggleStudy => {
+            let exam = state.exam.as_ref()?;
+            if exam.display.studying {
+                let exam = state.exam.as_mut()?;
+                exam.display.review_items = None;
+                exam.display.studying = false;
                 return None;
+            }
+
+            // Answers kept from an earlier review are asked for again
+            let day = srs::today();
+            let num_discarded = exam
+                .questions
+                .iter()
+                .filter(|item| item.is_due(day) && item.schedule().is_some() && item.has_answer())
+                .count();
+            match num_discarded {
+                0 => tx.send(Messages::StartStudy).unwrap(),
+                _ => {
+                    let message = format!(
+                        "Studying clears the answers of {} item(s) due for another review. Continue?",
+                        num_discarded
+                    );
+                    state.modal.confirm_modal_state =
+                        ConfirmModalState::Show(message, ConfirmAction::StartStudy);
+                }
             };
+            None
+        }
+        Messages::StartStudy => {
+            let exam = state.exam.as_mut()?;
+            let day = srs::today();
+            let due_items: Vec<usize> = exam
+                .order()
+                .into_iter()
+                .filter(|index| exam.questions[*index].is_due(day))
+                .collect();
+            let first_index = *due_items.first()?;
+
+            // Items reviewed before are answered from scratch, and the answers
+            // of new items are kept
+            due_items.iter().for_each(|index| {
+                if let Some(item) = exam.question_at_mut(*index) {
+                    if item.schedule().is_some() && item.has_answer() {
+                        item.reset_answer();
+                        exam.unsaved_changes = true;
+                    }
+                }
+            });
+            exam.display.question_index = first_index;
+            exam.display.question_scroll_pos = 0;
+            exam.display.card_flipped = false;
+            exam.display.display_answer = false;
+            exam.display.review_items = Some(due_items);
+            exam.display.studying = true;
+            None
+        }
+        Messages::ResetReviewItems => {
+            let exam = state.exam.as_mut()?;
+            let review_items = exam.display.review_items.clone()?;
+            review_items.iter().for_each(|index| {
+                if let Some(item) = exam.question_at_mut(*index) {
+                    item.reset_answer();
+                }
+            });
+
+            // Retries the questions with the answers hidden
+            exam.display.display_answer = false;
+            exam.display.card_flipped = false;
+            exam.unsaved_changes = true;
+            if let OpenMode::AutoSave = &state.home.open_mode {
+                save_state(state, tx.clone());
+            }
+            None
+        }
+        Messages::ToggleExamResult => {
+            let exam = state.exam.as_mut()?;
+            exam.display.display_answer = !exam.display.display_answer;
+            exam.display.answer_input = None;
+            if exam.display.display_answer {
+                exam.submit();
+            }
+
+            // Questions are reviewed once the answers are shown
+            if exam.display.display_answer && exam.display.studying {
+                let day = srs::today();
+                let scoring = exam.scoring;
+                exam.display.review_items.clone()?.iter().for_each(|index| {
+                    // Cards are reviewed once self-graded
+                    match exam.question_at_mut(*index) {
+                        Some(Item::Card(_)) | None => {}
+                        Some(item) => item.record_review(scoring, day),
+                    }
+                });
+                exam.unsaved_changes = true;
+                if let OpenMode::AutoSave = &state.home.open_mode {
+                    save_state(state, tx.clone());
+                }
+            }
+            None
+        }
+        Messages::UpdateHomeSelected(evt) => {
+            let num_paths = state.home.get_paths().unwrap().len();
             let selected = state.home.list_state.selected();
-            let next_index = match &evt {
-                UpdateListSelectedEvent::Next => selected
-                    .map(|selected| {
-                        if selected < max_index {
-                            selected + 1
-                        } else {
-                            0
-                        }
-                    })
-                    .unwrap_or(0),
-                UpdateListSelectedEvent::Prev => selected
-                    .map(|selected| {
-                        if selected > 0 {
-                            selected - 1
-                        } else {
-                            max_index
-                        }
-                    })
-                    .unwrap_or(0),
-                UpdateListSelectedEvent::Home => 0,
-                UpdateListSelectedEvent::End => max_index,
-            };
+            let next_index = next_list_index(selected, num_paths, &evt)?;
             state.home.list_state.select(Some(next_index));
             None
         }
+        Messages::Finder(action) => {
+            match action {
+                FinderActions::Open => {
+                    let root = state.home.current_path.clone();
+                    let max_depth = state.config.finder_depth;
+                    let tx = tx.clone();
+                    state.home.finder = Some(Finder::default());
+
+                    thread::spawn(move || {
+                        let entries = finder::index(&root, max_depth);
+                        tx.send(Messages::Finder(FinderActions::Indexed(root, entries)))
+                            .unwrap();
+                    });
+                }
+                FinderActions::Indexed(root, entries) => {
+                    // The finder may have been closed or reopened elsewhere since
+                    if root == state.home.current_path {
+                        state.home.finder.as_mut()?.set_entries(entries);
+                    }
+                }
+                FinderActions::Push(c) => {
+                    let finder = state.home.finder.as_mut()?;
+                    finder.query.push(c);
+                    finder.update_matches();
+                }
+                FinderActions::Pop => {
+                    let finder = state.home.finder.as_mut()?;
+                    finder.query.pop();
+                    finder.update_matches();
+                }
+                FinderActions::Select(evt) => {
+                    let finder = state.home.finder.as_mut()?;
+                    let selected = finder.list_state.selected();
+                    let next_index = next_list_index(selected, finder.matches.len(), &evt)?;
+                    finder.list_state.select(Some(next_index));
+                }
+                FinderActions::OpenFile => {
+                    let path = state.home.finder.as_ref()?.selected()?.path.clone();
+                    state.home.finder = None;
+                    state.home.select_path(&path);
+                    load_file(path, tx.clone());
+                }
+                FinderActions::Close => {
+                    state.home.finder = None;
+                }
+            }
+            None
+        }
+        Messages::UpdateResultsSelected(evt) => {
+            let exam = state.exam.as_ref()?;
+            let num_missed_items = Report::new(exam).missed_items.len();
+            let selected = state.results.list_state.selected();
+            let next_index = next_list_index(selected, num_missed_items, &evt)?;
+            state.results.list_state.select(Some(next_index));
+            None
+        }
+        Messages::ExportReport => {
+            let exam = state.exam.as_ref()?;
+            let exam_src = state.home.exam_src.as_ref()?;
+            let title = exam_src
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("???");
+            let contents = Report::new(exam).to_markdown(exam, title);
+            let filename = exam_src.with_file_name(format!("{}.report.md", title));
+            let tx = tx.clone();
+
+            thread::spawn(move || match fs::write(&filename, contents) {
+                Ok(()) => tx.send(Messages::ReportExported(filename)).unwrap(),
+                Err(err) => {
+                    let message = format!(
+                        "Unable to export \"{}\":\n{}",
+                        filename.to_str().unwrap_or("???"),
+                        err
+                    );
+                    tx.send(Messages::SaveFailed(message)).unwrap();
+                }
+            });
+            None
+        }
+        Messages::ReportExported(filename) => {
+            state.results.status = Some(format!(
+                "Report exported to {}",
+                filename.to_str().unwrap_or("???")
+            ));
+            None
+        }
         Messages::UpdateJumpboxValue(value) => {
             state.exam.as_mut().map(|exam| {
                 exam.jumpbox_value = value;
@@ -162,45 +705,7 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
                     state.home.current_path = filename.to_path_buf();
                     state.home.list_state.select(Some(0));
                 }
-                false => match filename.extension() {
-                    Some(ext) => match ext.to_str() {
-                        Some("exhaust") | Some("gz") => {
-                            let file = File::open(filename).expect("Unable to open file");
-                            let tx = tx.clone();
-
-                            thread::spawn(move || {
-                                let mut decoder =
-                                    Decoder::new(&file).expect("Unable to decompress the file");
-                                let mut contents = String::new();
-                                decoder
-                                    .read_to_string(&mut contents)
-                                    .expect("Unable to decode the file with utf-8");
-
-                                let exam: Exam = serde_json::from_str(&contents)
-                                    .expect("Unable to convert file to string");
-                                tx.send(Messages::FileLoaded(exam)).unwrap();
-                                tx.send(Messages::ChangeRoute(AppRoute::DoExam)).unwrap();
-                            });
-                        }
-                        Some("json") => {
-                            // Parse Json
-                            let mut file = File::open(filename).expect("Unable to open file");
-                            let tx = tx.clone();
-
-                            thread::spawn(move || {
-                                let mut contents = String::new();
-                                file.read_to_string(&mut contents)
-                                    .expect("Unable to read file");
-                                let exam: Exam = serde_json::from_str(&contents)
-                                    .expect("Unable to convert file to string");
-                                tx.send(Messages::FileLoaded(exam)).unwrap();
-                                tx.send(Messages::ChangeRoute(AppRoute::DoExam)).unwrap();
-                            });
-                        }
-                        _ => {}
-                    },
-                    _ => {}
-                },
+                false => load_file(filename, tx.clone()),
             };
 
             None
@@ -209,10 +714,45 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
             state.home.open_mode = mode;
             None
         }
-        Messages::FileLoaded(exam) => {
-            state.exam = Some(exam);
+        Messages::ToggleShuffle => {
+            state.home.shuffle = !state.home.shuffle;
             None
         }
+        Messages::FileLoaded(filename, mut exam) => {
+            // The seed is saved so that the exam keeps its order when reopened
+            if state.home.shuffle && exam.shuffle_seed.is_none() {
+                exam.shuffle_seed = Some(shuffle::new_seed());
+                exam.unsaved_changes = true;
+            }
+            exam.apply_shuffle();
+            state.home.exam_src = Some(filename);
+            state.exam = Some(*exam);
+            None
+        }
+        Messages::LoadFailed(message) | Messages::SaveFailed(message) => {
+            state.modal.error_modal_state = ErrorModalState::Show(message);
+            None
+        }
+        Messages::ConfirmModalAction(action) => {
+            let modal_state = std::mem::replace(
+                &mut state.modal.confirm_modal_state,
+                ConfirmModalState::Hidden,
+            );
+            if let (ConfirmModalActions::Okay, ConfirmModalState::Show(_, confirm_action)) =
+                (action, modal_state)
+            {
+                match confirm_action {
+                    ConfirmAction::StartStudy => tx.send(Messages::StartStudy).unwrap(),
+                }
+            }
+            None
+        }
+        Messages::ErrorModalAction(action) => match action {
+            ErrorModalActions::Close => {
+                state.modal.error_modal_state = ErrorModalState::Hidden;
+                None
+            }
+        },
         Messages::SaveModalAction(action) => match action {
             SaveModalActions::Open(modal_state) => {
                 state.modal.save_modal_state = modal_state;
@@ -236,6 +776,39 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
                 state.modal.save_modal_state = SaveModalState::Hidden;
                 None
             }
+            SaveModalActions::SaveAs(filename) => {
+                // Relative names are next to the exam
+                let filename = match state.home.exam_src.as_ref().and_then(|src| src.parent()) {
+                    Some(dir) => dir.join(filename),
+                    None => filename,
+                };
+                match filename.extension().and_then(|ext| ext.to_str()) {
+                    Some("json") | Some("exhaust") | Some("gz") => {
+                        state.modal.save_modal_state = match filename.exists()
+                            && state.home.exam_src.as_ref() != Some(&filename)
+                        {
+                            true => SaveModalState::ShowOverwrite(filename),
+                            false => {
+                                save_state_as(state, filename, tx.clone());
+                                SaveModalState::Hidden
+                            }
+                        };
+                    }
+                    _ => {
+                        let message = format!(
+                            "Unable to save \"{}\":\nThe file name should end with .json, .exhaust or .gz",
+                            filename.to_str().unwrap_or("???")
+                        );
+                        tx.send(Messages::SaveFailed(message)).unwrap();
+                    }
+                };
+                None
+            }
+            SaveModalActions::Overwrite(filename) => {
+                save_state_as(state, filename, tx.clone());
+                state.modal.save_modal_state = SaveModalState::Hidden;
+                None
+            }
             SaveModalActions::Cancel => {
                 state.modal.save_modal_state = SaveModalState::Hidden;
                 None
@@ -248,7 +821,10 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
                 let num_assets = current_item.get_assets().len();
 
                 if num_assets > 0 {
-                    state.modal.assets_modal_state = AssetsModalState::Show(ListState::default());
+                    let mut list_state = ListState::default();
+                    list_state.select(Some(0));
+                    let preview = load_preview(state, 0, &tx);
+                    state.modal.assets_modal_state = AssetsModalState::Show(list_state, preview);
                 }
                 None
             }
@@ -261,70 +837,131 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
                 let current_item = exam.question_at(exam.display.question_index).unwrap();
                 let assets = current_item.get_assets();
 
-                let max_index = if assets.len() > 0 {
-                    assets.len() - 1
-                } else {
-                    return None;
+                let selected = match &state.modal.assets_modal_state {
+                    AssetsModalState::Show(list_state, _) => list_state.selected(),
+                    _ => return None,
                 };
+                let next_index = next_list_index(selected, assets.len(), &evt)?;
+                let next_preview = load_preview(state, next_index, &tx);
 
-                let next_index = {
-                    let selected = match &state.modal.assets_modal_state {
-                        AssetsModalState::Show(list_state) => list_state.selected(),
-                        AssetsModalState::Hidden => return None,
-                    };
-
-                    match &evt {
-                        UpdateListSelectedEvent::Next => selected
-                            .map(|selected| {
-                                if selected < max_index {
-                                    selected + 1
-                                } else {
-                                    0
-                                }
-                            })
-                            .unwrap_or(0),
-                        UpdateListSelectedEvent::Prev => selected
-                            .map(|selected| {
-                                if selected > 0 {
-                                    selected - 1
-                                } else {
-                                    max_index
-                                }
-                            })
-                            .unwrap_or(0),
-                        UpdateListSelectedEvent::Home => 0,
-                        UpdateListSelectedEvent::End => max_index,
-                    }
+                if let AssetsModalState::Show(list_state, preview) =
+                    &mut state.modal.assets_modal_state
+                {
+                    list_state.select(Some(next_index));
+                    *preview = next_preview;
+                };
+                None
+            }
+            AssetsModalActions::TogglePreview => {
+                let selected = match &state.modal.assets_modal_state {
+                    AssetsModalState::Show(list_state, _) => list_state.selected(),
+                    _ => return None,
                 };
+                state.config.image_preview = !state.config.image_preview;
+                let next_preview = selected.and_then(|selected| load_preview(state, selected, &tx));
 
-                if let AssetsModalState::Show(list_state) = &mut state.modal.assets_modal_state {
-                    list_state.select(Some(next_index));
+                if let AssetsModalState::Show(_, preview) = &mut state.modal.assets_modal_state {
+                    *preview = next_preview;
                 };
                 None
             }
             AssetsModalActions::OpenFile => {
-                let launcher = &state.config.launcher;
-                let current_path = &state.home.current_path;
-
-                let assets_list_state = match &state.modal.assets_modal_state {
-                    AssetsModalState::Show(list_state) => list_state,
-                    AssetsModalState::Hidden => unreachable!(),
+                let list_state = match &state.modal.assets_modal_state {
+                    AssetsModalState::Show(list_state, _) => list_state.clone(),
+                    _ => return None,
                 };
                 let exam = state.exam.as_ref().unwrap();
-                exam.question_at(exam.display.question_index)
-                    .map(|current_item| current_item.get_assets())
-                    .and_then(|assets| {
-                        let selected = assets_list_state.selected()?;
-                        let current_file = assets.get(selected)?;
-                        Command::new(launcher)
-                            .arg(current_path.join(current_file))
-                            .spawn()
-                            .ok()
-                    });
+                let asset =
+                    exam.question_at(exam.display.question_index)
+                        .and_then(|current_item| {
+                            current_item.get_assets().get(list_state.selected()?)
+                        })?;
+                let path = state.asset_path(asset);
 
+                // Text is shown in place, anything else is left to the launcher
+                let text_view = match preview::is_image(&path) {
+                    true => None,
+                    false => TextView::load(&path, asset).ok(),
+                };
+                match text_view {
+                    Some(text_view) => {
+                        state.modal.assets_modal_state =
+                            AssetsModalState::View(list_state, text_view);
+                    }
+                    None => {
+                        Command::new(&state.config.launcher).arg(path).spawn().ok();
+                    }
+                }
+                None
+            }
+            AssetsModalActions::View(action) => {
+                let text_view = match &mut state.modal.assets_modal_state {
+                    AssetsModalState::View(_, text_view) => text_view,
+                    _ => return None,
+                };
+                let page = text_view.height.max(1) as isize;
+                match action {
+                    TextViewActions::Scroll(delta) => text_view.scroll_by(delta),
+                    TextViewActions::PageDown => text_view.scroll_by(page),
+                    TextViewActions::PageUp => text_view.scroll_by(-page),
+                    TextViewActions::Home => text_view.scroll = 0,
+                    TextViewActions::End => text_view.scroll_by(text_view.lines.len() as isize),
+                    TextViewActions::Search(evt) => match evt {
+                        UpdateSearchEvent::Open => text_view.search_input = Some(String::new()),
+                        UpdateSearchEvent::Push(c) => {
+                            if let Some(input) = text_view.search_input.as_mut() {
+                                input.push(c);
+                            }
+                        }
+                        UpdateSearchEvent::Pop => {
+                            text_view.search_input.as_mut().map(|input| input.pop());
+                        }
+                        UpdateSearchEvent::Cancel => text_view.search_input = None,
+                        UpdateSearchEvent::Submit => {
+                            let input = text_view.search_input.take().unwrap_or_default();
+                            text_view.search = input.to_lowercase();
+                            // Searches from the top of the visible lines
+                            text_view.current_match = None;
+                            text_view.find(true);
+                        }
+                    },
+                    TextViewActions::NextMatch => {
+                        text_view.find(true);
+                    }
+                    TextViewActions::PrevMatch => {
+                        text_view.find(false);
+                    }
+                    TextViewActions::Close => {
+                        let list_state = match &state.modal.assets_modal_state {
+                            AssetsModalState::View(list_state, _) => list_state.clone(),
+                            _ => return None,
+                        };
+                        let preview = list_state
+                            .selected()
+                            .and_then(|selected| load_preview(state, selected, &tx));
+                        state.modal.assets_modal_state =
+                            AssetsModalState::Show(list_state, preview);
+                    }
+                }
                 None
             }
         },
+        Messages::PreviewLoaded(path, image) => {
+            // Drops the images of assets no longer selected
+            if let AssetsModalState::Show(_, preview) = &mut state.modal.assets_modal_state {
+                match preview {
+                    Some(AssetPreview::Loading(loading)) if *loading == path => {
+                        *preview = Some(AssetPreview::Loaded(image));
+                    }
+                    _ => {}
+                }
+            }
+            None
+        }
+        Messages::SavedAs(filename) => {
+            state.home.exam_src = Some(filename);
+            None
+        }
         Messages::UnsavedChanges(uc) => {
             state.exam.as_mut().map(|exam| {
                 exam.unsaved_changes = uc;
@@ -335,37 +972,430 @@ pub fn reduce(state: &mut App, event: Messages, tx: mpsc::Sender<Messages>) -> O
     }
 }
 
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Decompress(io::Error),
+    Parse(serde_json::Error),
+    /// An invalid regular expression, in the item at the index
+    Pattern(usize, regex::Error),
+    UnsupportedType,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "{}", err),
+            LoadError::Decompress(err) => write!(f, "Unable to decompress the file: {}", err),
+            // `serde_json` reports the line and column of the error
+            LoadError::Parse(err) => write!(f, "Invalid exam file: {}", err),
+            LoadError::Pattern(index, err) => {
+                write!(f, "Invalid pattern in item {}: {}", index + 1, err)
+            }
+            LoadError::UnsupportedType => write!(f, "Unsupported file type"),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Limits navigation to the items matching `filter`, or leaves the review if
+/// one is active. Nothing happens if no item matches.
+fn toggle_review(exam: &mut Exam, filter: impl Fn(&Item) -> bool) {
+    if exam.display.review_items.is_some() {
+        exam.display.review_items = None;
+        exam.display.studying = false;
+        return;
+    }
+    let review_items: Vec<usize> = exam
+        .order()
+        .into_iter()
+        .filter(|index| filter(&exam.questions[*index]))
+        .collect();
+    if let Some(first_index) = review_items.first() {
+        if !review_items.contains(&exam.display.question_index) {
+            exam.display.question_index = *first_index;
+            exam.display.question_scroll_pos = 0;
+            exam.display.card_flipped = false;
+        }
+        exam.display.review_items = Some(review_items);
+    }
+}
+
+/// Starts decoding the asset at `index` of the current item for a preview,
+/// if previews are enabled and the asset is an image.
+fn load_preview(state: &App, index: usize, tx: &mpsc::Sender<Messages>) -> Option<AssetPreview> {
+    if !state.config.image_preview {
+        return None;
+    }
+    let exam = state.exam.as_ref()?;
+    let current_item = exam.question_at(exam.display.question_index)?;
+    let path = state.asset_path(current_item.get_assets().get(index)?);
+    if !preview::is_image(&path) {
+        return None;
+    }
+
+    // Large images would stall the input while scrolling through assets
+    let tx = tx.clone();
+    let image_path = path.clone();
+    thread::spawn(move || {
+        let image = Image::load(&image_path);
+        tx.send(Messages::PreviewLoaded(image_path, image)).unwrap();
+    });
+    Some(AssetPreview::Loading(path))
+}
+
+/// Loads an exam in the background, then opens it.
+fn load_file(filename: PathBuf, tx: mpsc::Sender<Messages>) {
+    thread::spawn(move || match load_exam(&filename) {
+        Ok(exam) => {
+            tx.send(Messages::FileLoaded(filename, Box::new(exam)))
+                .unwrap();
+            tx.send(Messages::ChangeRoute(AppRoute::DoExam)).unwrap();
+        }
+        Err(err) => {
+            let message = format!(
+                "Unable to load \"{}\":\n{}",
+                filename.to_str().unwrap_or("???"),
+                err
+            );
+            tx.send(Messages::LoadFailed(message)).unwrap();
+        }
+    });
+}
+
+/// Returns the index selected after `evt` in a list of `len` items, wrapping
+/// around at both ends.
+fn next_list_index(
+    selected: Option<usize>,
+    len: usize,
+    evt: &UpdateListSelectedEvent,
+) -> Option<usize> {
+    let max_index = if len > 0 {
+        len - 1
+    } else {
+        return None;
+    };
+    let next_index = match evt {
+        UpdateListSelectedEvent::Next => selected
+            .map(|selected| {
+                if selected < max_index {
+                    selected + 1
+                } else {
+                    0
+                }
+            })
+            .unwrap_or(0),
+        UpdateListSelectedEvent::Prev => selected
+            .map(|selected| {
+                if selected > 0 {
+                    selected - 1
+                } else {
+                    max_index
+                }
+            })
+            .unwrap_or(0),
+        UpdateListSelectedEvent::Home => 0,
+        UpdateListSelectedEvent::End => max_index,
+        UpdateListSelectedEvent::To(index) => (*index).min(max_index),
+    };
+    Some(next_index)
+}
+
+/// Reads an exam from `filename`, decompressing it if it has an `.exhaust` or
+/// `.gz` extension.
+pub fn load_exam(filename: &Path) -> Result<Exam, LoadError> {
+    let mut contents = String::new();
+    match filename.extension().and_then(|ext| ext.to_str()) {
+        Some("exhaust") | Some("gz") => {
+            let file = File::open(filename).map_err(LoadError::Io)?;
+            let mut decoder = Decoder::new(&file).map_err(LoadError::Decompress)?;
+            decoder
+                .read_to_string(&mut contents)
+                .map_err(LoadError::Decompress)?;
+        }
+        Some("json") => {
+            let mut file = File::open(filename).map_err(LoadError::Io)?;
+            file.read_to_string(&mut contents).map_err(LoadError::Io)?;
+        }
+        _ => return Err(LoadError::UnsupportedType),
+    };
+    let mut exam: Exam = serde_json::from_str(&contents).map_err(LoadError::Parse)?;
+    exam.compile_patterns()
+        .map_err(|(index, err)| LoadError::Pattern(index, err))?;
+    Ok(exam)
+}
+
 pub fn save_state(state: &App, tx: mpsc::Sender<Messages>) -> Option<JoinHandle<()>> {
     // Save data on selection change
-    let exam_copy = state.exam.clone();
-    let maybe_filename = state.home.get_selected_path();
+    let filename = state.home.exam_src.clone()?;
+    save_exam(state, filename, false, tx)
+}
+
+/// Saves the exam to another file, which becomes its source once written.
+fn save_state_as(
+    state: &App,
+    filename: PathBuf,
+    tx: mpsc::Sender<Messages>,
+) -> Option<JoinHandle<()>> {
+    save_exam(state, filename, true, tx)
+}
+
+fn save_exam(
+    state: &App,
+    filename: PathBuf,
+    save_as: bool,
+    tx: mpsc::Sender<Messages>,
+) -> Option<JoinHandle<()>> {
+    let exam_copy = state.exam.clone()?;
     let pretty_printing = state.config.pretty_printing;
+    let backup_on_save = state.config.backup_on_save;
+    let save_queue = state.save_queue.clone();
+    let ticket = save_queue.issue();
 
     Some(thread::spawn(move || {
-        maybe_filename.map(|filename| {
-            let file = File::create(&filename)
-                .expect(&format!("Error opening {}", &filename.to_str().unwrap()));
-            match filename.extension() {
-                Some(ext) => match ext.to_str() {
-                    Some("json") => match pretty_printing {
-                        false => serde_json::to_writer(&file, &exam_copy),
-                        true => serde_json::to_writer_pretty(&file, &exam_copy),
-                    }
-                    .expect(&format!("Error writing {}", &filename.to_str().unwrap())),
-                    Some("exhaust") | Some("gz") => {
-                        let mut encoder = Encoder::new(file).expect("Unable to initialize encoder");
-                        match pretty_printing {
-                            false => serde_json::to_writer(&mut encoder, &exam_copy),
-                            true => serde_json::to_writer_pretty(&mut encoder, &exam_copy),
-                        }
-                        .expect("Unable to write to file");
-                        encoder.finish();
-                    }
-                    _ => {}
-                },
-                _ => {}
-            };
-            tx.send(Messages::UnsavedChanges(false)).unwrap();
+        let result = save_queue.write(ticket, || {
+            write_exam(&filename, &exam_copy, pretty_printing, backup_on_save)
         });
+        match result {
+            Some(Ok(())) => {
+                if save_as {
+                    tx.send(Messages::SavedAs(filename)).unwrap();
+                }
+                // Later changes are still unsaved if another save is pending
+                if save_queue.is_latest(ticket) {
+                    tx.send(Messages::UnsavedChanges(false)).unwrap();
+                }
+            }
+            Some(Err(err)) => {
+                let message = format!(
+                    "Unable to save \"{}\":\n{}",
+                    filename.to_str().unwrap_or("???"),
+                    err
+                );
+                tx.send(Messages::SaveFailed(message)).unwrap();
+            }
+            // Superseded by a newer save
+            None => {}
+        }
     }))
 }
+
+/// Writes `exam` to a temporary file next to `filename`, syncs it to disk,
+/// and renames it over `filename`, so that an interrupted save never leaves a
+/// truncated file behind.
+fn write_exam(
+    filename: &Path,
+    exam: &Exam,
+    pretty_printing: bool,
+    backup_on_save: bool,
+) -> io::Result<()> {
+    let dir = match filename.parent() {
+        Some(dir) if dir != Path::new("") => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let basename = filename
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Invalid file name",
+        ))?;
+    let tmp_filename = dir.join(format!(".{}.tmp", basename));
+
+    let write_tmp = || -> io::Result<()> {
+        let file = File::create(&tmp_filename)?;
+        let file = match filename.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => {
+                let mut writer = BufWriter::new(file);
+                match pretty_printing {
+                    false => serde_json::to_writer(&mut writer, exam),
+                    true => serde_json::to_writer_pretty(&mut writer, exam),
+                }?;
+                writer.into_inner().map_err(|err| err.into_error())?
+            }
+            Some("exhaust") | Some("gz") => {
+                let mut encoder = Encoder::new(BufWriter::new(file))?;
+                match pretty_printing {
+                    false => serde_json::to_writer(&mut encoder, exam),
+                    true => serde_json::to_writer_pretty(&mut encoder, exam),
+                }?;
+                let writer = encoder.finish().into_result()?;
+                writer.into_inner().map_err(|err| err.into_error())?
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Unsupported file type",
+                ))
+            }
+        };
+        file.sync_all()
+    };
+    if let Err(err) = write_tmp() {
+        let _ = fs::remove_file(&tmp_filename);
+        return Err(err);
+    }
+
+    // Keeps the previous version of the file
+    if backup_on_save && filename.exists() {
+        fs::copy(filename, dir.join(format!("{}.bak", basename)))?;
+    }
+
+    fs::rename(&tmp_filename, filename)?;
+
+    // Persists the rename itself
+    #[cfg(unix)]
+    File::open(&dir)?.sync_all()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the app taking a timed exam of a single question.
+    fn timed_app() -> App {
+        let exam: Exam = serde_json::from_str(
+            r#"{"time_limit": 600, "questions": [{"type": "Question", "question": "?", "selections": [
+                {"text": "a", "should_select": true},
+                {"text": "b"}
+            ], "answer": null}]}"#,
+        )
+        .unwrap();
+        App {
+            exam: Some(exam),
+            route: AppRoute::DoExam,
+            ..App::default()
+        }
+    }
+
+    /// Returns the app taking an exam scored partially, whose questions are
+    /// answered right, partially right, wrong, and not at all but flagged.
+    fn review_app() -> App {
+        let question = r#"{"type": "Question", "question": "?", "selections": [
+            {"text": "a", "should_select": true},
+            {"text": "b", "should_select": true},
+            {"text": "c"}
+        ], "answer": null}"#;
+        let mut exam: Exam = serde_json::from_str(&format!(
+            r#"{{"scoring": "Partial", "questions": [{0}, {0}, {0}, {0}]}}"#,
+            question
+        ))
+        .unwrap();
+        let answers: [&[usize]; 4] = [&[0, 1], &[0], &[2], &[]];
+        for (index, selected) in answers.iter().enumerate() {
+            if let Some(Item::Question(question)) = exam.question_at_mut(index) {
+                for selection in selected.iter() {
+                    question.user_selection |= SelectionFlags::from_index(*selection).unwrap();
+                }
+                question.flagged = selected.is_empty();
+            }
+        }
+        App {
+            exam: Some(exam),
+            route: AppRoute::DoExam,
+            ..App::default()
+        }
+    }
+
+    fn review_items(state: &App) -> Option<Vec<usize>> {
+        state.exam.as_ref().unwrap().display.review_items.clone()
+    }
+
+    fn user_selection(state: &App) -> SelectionFlags {
+        match state.exam.as_ref().unwrap().question_at(0) {
+            Some(Item::Question(question)) => question.user_selection,
+            _ => panic!("Not a question"),
+        }
+    }
+
+    #[test]
+    fn timed_exam_stays_submitted_once_results_are_shown() {
+        let mut state = timed_app();
+        let (tx, _rx) = mpsc::channel();
+        let sel = SelectionFlags::from_index(0).unwrap();
+
+        reduce(&mut state, Messages::ToggleSelection(sel), tx.clone());
+        assert_eq!(user_selection(&state), sel);
+
+        // Show, then hide the results
+        reduce(&mut state, Messages::ToggleExamResult, tx.clone());
+        reduce(&mut state, Messages::ToggleExamResult, tx.clone());
+        assert!(state.exam.as_ref().unwrap().is_submitted());
+
+        reduce(&mut state, Messages::ToggleSelection(sel), tx.clone());
+        assert_eq!(user_selection(&state), sel);
+        let elapsed_time = state.exam.as_ref().unwrap().elapsed_time;
+        reduce(&mut state, Messages::Tick, tx);
+        assert_eq!(state.exam.as_ref().unwrap().elapsed_time, elapsed_time);
+    }
+
+    #[test]
+    fn untimed_exam_is_never_submitted() {
+        let mut state = timed_app();
+        let (tx, _rx) = mpsc::channel();
+        state.exam.as_mut().unwrap().time_limit = None;
+        reduce(&mut state, Messages::ToggleExamResult, tx.clone());
+        reduce(&mut state, Messages::ToggleExamResult, tx);
+        assert!(!state.exam.as_ref().unwrap().is_submitted());
+    }
+
+    #[test]
+    fn review_includes_partially_right_answers() {
+        let mut state = review_app();
+        let (tx, _rx) = mpsc::channel();
+        reduce(&mut state, Messages::ToggleReview, tx.clone());
+        assert_eq!(review_items(&state), Some(vec![1, 2]));
+        assert_eq!(state.exam.as_ref().unwrap().display.question_index, 1);
+
+        reduce(&mut state, Messages::ToggleReview, tx);
+        assert_eq!(review_items(&state), None);
+    }
+
+    #[test]
+    fn flagged_review_is_limited_to_flagged_items() {
+        let mut state = review_app();
+        let (tx, _rx) = mpsc::channel();
+        reduce(&mut state, Messages::ToggleFlaggedReview, tx.clone());
+        assert_eq!(review_items(&state), Some(vec![3]));
+        assert_eq!(state.exam.as_ref().unwrap().display.question_index, 3);
+
+        reduce(&mut state, Messages::ToggleFlaggedReview, tx.clone());
+        assert_eq!(review_items(&state), None);
+
+        // Nothing to review without flagged items
+        if let Some(Item::Question(question)) = state.exam.as_mut().unwrap().question_at_mut(3) {
+            question.flagged = false;
+        }
+        reduce(&mut state, Messages::ToggleFlaggedReview, tx);
+        assert_eq!(review_items(&state), None);
+    }
+
+    #[test]
+    fn new_shuffle_seed_is_unsaved() {
+        let mut state = App::default();
+        let (tx, _rx) = mpsc::channel();
+        let exam = timed_app().exam.unwrap();
+        state.home.shuffle = true;
+        reduce(
+            &mut state,
+            Messages::FileLoaded("exam.json".into(), Box::new(exam)),
+            tx.clone(),
+        );
+        let exam = state.exam.take().unwrap();
+        assert!(exam.shuffle_seed.is_some());
+        assert!(exam.unsaved_changes);
+
+        // Exams shuffled before keep their seed
+        let mut exam = exam;
+        exam.unsaved_changes = false;
+        reduce(
+            &mut state,
+            Messages::FileLoaded("exam.json".into(), Box::new(exam)),
+            tx,
+        );
+        assert!(!state.exam.as_ref().unwrap().unsaved_changes);
+    }
+}
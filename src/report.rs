@@ -0,0 +1,167 @@
+use crate::app::*;
+use std::collections::BTreeMap;
+
+/// Result counts of a group of items.
+#[derive(Clone, Debug, Default)]
+pub struct Tally {
+    pub correct: usize,
+    pub partial: usize,
+    pub wrong: usize,
+    pub pending: usize,
+    pub points: f64,
+    pub max_points: f64,
+}
+
+impl Tally {
+    fn add(&mut self, item: &Item, scoring: ScoringPolicy) {
+        match item.get_result(scoring) {
+            QuestionResult::Correct => self.correct += 1,
+            QuestionResult::Partial => self.partial += 1,
+            QuestionResult::Wrong => self.wrong += 1,
+            QuestionResult::Pending | QuestionResult::Done => self.pending += 1,
+        }
+        self.points += item.get_score(scoring).unwrap_or(0f64);
+        self.max_points += item.max_points();
+    }
+
+    /// Percentage of points earned out of the points of all items.
+    pub fn percentage(&self) -> f64 {
+        match self.max_points > 0f64 {
+            true => self.points * 100f64 / self.max_points,
+            false => 0f64,
+        }
+    }
+}
+
+/// The summary of an exam, shown in the results screen.
+pub struct Report {
+    pub tally: Tally,
+    /// Indices of items answered wrongly or partially
+    pub missed_items: Vec<usize>,
+    pub tags: BTreeMap<String, Tally>,
+}
+
+impl Report {
+    pub fn new(exam: &Exam) -> Self {
+        let mut tally = Tally::default();
+        let mut missed_items = vec![];
+        let mut tags: BTreeMap<String, Tally> = BTreeMap::new();
+
+        exam.questions.iter().enumerate().for_each(|(index, item)| {
+            tally.add(item, exam.scoring);
+            match item.get_result(exam.scoring) {
+                QuestionResult::Wrong | QuestionResult::Partial => missed_items.push(index),
+                _ => {}
+            }
+            item.get_tags().into_iter().for_each(|tag| {
+                tags.entry(tag.to_owned())
+                    .or_default()
+                    .add(item, exam.scoring);
+            });
+        });
+
+        Report {
+            tally,
+            missed_items,
+            tags,
+        }
+    }
+
+    pub fn to_markdown(&self, exam: &Exam, title: &str) -> String {
+        let mut lines = vec![
+            format!("# Results: {}", title),
+            String::new(),
+            format!("- Correct: {}", self.tally.correct),
+            format!("- Partially correct: {}", self.tally.partial),
+            format!("- Wrong: {}", self.tally.wrong),
+            format!("- Pending: {}", self.tally.pending),
+            format!(
+                "- Score: {}/{} ({:.1}%)",
+                format_points(self.tally.points),
+                format_points(self.tally.max_points),
+                self.tally.percentage()
+            ),
+        ];
+
+        if !self.missed_items.is_empty() {
+            lines.push(String::new());
+            lines.push("## Wrong and partial answers".to_owned());
+            lines.push(String::new());
+            self.missed_items.iter().for_each(|index| {
+                let text = exam
+                    .question_at(*index)
+                    .map(|item| missed_summary(item, exam.scoring))
+                    .unwrap_or_default();
+                lines.push(format!("{}. {}", exam.display_number(*index), text));
+            });
+        }
+
+        if !self.tags.is_empty() {
+            lines.push(String::new());
+            lines.push("## By tag".to_owned());
+            lines.push(String::new());
+            lines.push("| Tag | Correct | Partial | Wrong | Pending | Score |".to_owned());
+            lines.push("| --- | --- | --- | --- | --- | --- |".to_owned());
+            self.tags.iter().for_each(|(tag, tally)| {
+                lines.push(format!(
+                    "| {} | {} | {} | {} | {} | {:.1}% |",
+                    escape_cell(tag),
+                    tally.correct,
+                    tally.partial,
+                    tally.wrong,
+                    tally.pending,
+                    tally.percentage()
+                ));
+            });
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Formats points with at most two decimal places.
+pub fn format_points(points: f64) -> String {
+    let text = format!("{:.2}", points);
+    text.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
+
+/// Escapes `text` to fit in a cell of a Markdown table.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Summarizes a missed item, marking the partially correct ones.
+pub fn missed_summary(item: &Item, scoring: ScoringPolicy) -> String {
+    let text = summarize(item.get_text());
+    match item.get_result(scoring) {
+        QuestionResult::Partial => format!("{} (partial)", text),
+        _ => text,
+    }
+}
+
+/// Returns the first line of `text`, shortened to fit in a list.
+pub fn summarize(text: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = text.lines().next().unwrap_or("").trim();
+    match line.chars().count() > MAX_CHARS {
+        true => format!("{}...", line.chars().take(MAX_CHARS).collect::<String>()),
+        false => line.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_table_cells() {
+        assert_eq!(escape_cell("a|b"), "a\\|b");
+        assert_eq!(escape_cell("first\n second"), "first second");
+        assert_eq!(escape_cell("plain"), "plain");
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * shuffle.rs
+ *
+ * Seeded shuffling of questions and selections.
+ *
+ * A recorded seed must give the same order on every run, so this uses its own
+ * SplitMix64 generator rather than a library whose output may change between
+ * versions.
+ */
+use std::time::{SystemTime, UNIX_EPOCH};
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE5_E4B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Returns a shuffled list of `0..len`, determined by `seed`.
+pub fn permutation(len: usize, seed: u64) -> Vec<usize> {
+    let mut rng = SplitMix64(seed);
+    let mut result: Vec<usize> = (0..len).collect();
+    // Fisher-Yates
+    for i in (1..len).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        result.swap(i, j);
+    }
+    result
+}
+
+/// Derives the seed of a sub-sequence, e.g. the selections of the `index`-th
+/// question, from the seed of the exam.
+pub fn derive_seed(seed: u64, index: usize) -> u64 {
+    SplitMix64(seed ^ (index as u64).wrapping_add(1)).next_u64()
+}
+
+pub fn new_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation_is_stable_for_a_seed() {
+        // A recorded seed must keep giving the same order
+        assert_eq!(permutation(6, 42), permutation(6, 42));
+        assert_eq!(permutation(6, 42), vec![3, 4, 1, 5, 0, 2]);
+    }
+
+    #[test]
+    fn permutation_keeps_every_index() {
+        let mut order = permutation(26, 7);
+        order.sort_unstable();
+        assert_eq!(order, (0..26).collect::<Vec<_>>());
+        assert_eq!(permutation(0, 7), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn derived_seeds_differ() {
+        assert_eq!(derive_seed(42, 3), derive_seed(42, 3));
+        assert_ne!(derive_seed(42, 0), derive_seed(42, 1));
+    }
+}
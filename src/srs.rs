@@ -0,0 +1,143 @@
+/*
+ * srs.rs
+ *
+ * Spaced repetition scheduling with the SM-2 algorithm.
+ *
+ * Days are counted from the unix epoch, so that a schedule does not depend
+ * on the time zone it was saved in.
+ */
+use crate::app::{CardGrade, QuestionResult};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const DEFAULT_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReviewLog {
+    pub day: u64,
+    /// Recall quality from 0 (blackout) to 5 (perfect)
+    pub quality: u8,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Schedule {
+    pub ease: f64,
+    /// Days until the next review
+    pub interval: u64,
+    /// Number of successful reviews in a row
+    pub repetitions: u32,
+    pub due_day: u64,
+    #[serde(default)]
+    pub history: Vec<ReviewLog>,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule {
+            ease: DEFAULT_EASE,
+            interval: 0,
+            repetitions: 0,
+            due_day: 0,
+            history: vec![],
+        }
+    }
+}
+
+impl Schedule {
+    pub fn is_due(&self, day: u64) -> bool {
+        self.due_day <= day
+    }
+
+    pub fn last_review_day(&self) -> Option<u64> {
+        self.history.last().map(|log| log.day)
+    }
+
+    /// Records a review of `quality` on `day` and schedules the next one.
+    pub fn review(&mut self, quality: u8, day: u64) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.repetitions += 1;
+            self.interval = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval as f64 * self.ease).round() as u64,
+            };
+        } else {
+            self.repetitions = 0;
+            self.interval = 1;
+        }
+
+        let penalty = (5 - quality) as f64;
+        self.ease = (self.ease + 0.1 - penalty * (0.08 + penalty * 0.02)).max(MIN_EASE);
+        self.due_day = day + self.interval;
+        self.history.push(ReviewLog { day, quality });
+    }
+}
+
+/// Returns the number of days since the unix epoch.
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or(0)
+}
+
+/// Returns the recall quality of an answered question, or `None` if pending.
+pub fn quality_of_result(result: &QuestionResult) -> Option<u8> {
+    match result {
+        QuestionResult::Correct => Some(5),
+        QuestionResult::Partial => Some(3),
+        QuestionResult::Wrong => Some(1),
+        QuestionResult::Pending | QuestionResult::Done => None,
+    }
+}
+
+/// Returns the recall quality of a self-graded card, or `None` if pending.
+pub fn quality_of_grade(grade: &CardGrade) -> Option<u8> {
+    match grade {
+        CardGrade::Known => Some(4),
+        CardGrade::Unknown => Some(1),
+        CardGrade::Pending => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_grow_with_successful_reviews() {
+        let mut schedule = Schedule::default();
+        schedule.review(5, 100);
+        assert_eq!((schedule.interval, schedule.due_day), (1, 101));
+        schedule.review(5, 101);
+        assert_eq!((schedule.interval, schedule.due_day), (6, 107));
+        schedule.review(5, 107);
+        // 6 days times an ease of 2.7
+        assert_eq!((schedule.interval, schedule.due_day), (16, 123));
+        assert_eq!(schedule.repetitions, 3);
+        assert_eq!(schedule.last_review_day(), Some(107));
+    }
+
+    #[test]
+    fn failed_review_starts_over() {
+        let mut schedule = Schedule::default();
+        schedule.review(5, 0);
+        schedule.review(5, 1);
+        schedule.review(1, 7);
+        assert_eq!(schedule.repetitions, 0);
+        assert_eq!((schedule.interval, schedule.due_day), (1, 8));
+        assert!(!schedule.is_due(7));
+        assert!(schedule.is_due(8));
+    }
+
+    #[test]
+    fn ease_has_a_minimum() {
+        let mut schedule = Schedule::default();
+        (0..10).for_each(|day| schedule.review(0, day));
+        assert_eq!(schedule.ease, MIN_EASE);
+        assert_eq!(schedule.history.len(), 10);
+    }
+}
@@ -0,0 +1,462 @@
+/*
+ * theme.rs
+ *
+ * Colours of the interface, configurable in the `theme` section of
+ * `exhaust.json`.
+ *
+ * A theme starts from one of the named palettes: `dark`, `light`, or `mono`
+ * for monochrome terminals and high contrast. Single styles are then
+ * overridden by name, e.g. `"correct": { "fg": "LightGreen" }`, replacing
+ * only the parts given.
+ *
+ * Terminals without truecolor get RGB colours as the nearest of the 256
+ * indexed colours. Support is detected from `COLORTERM`, unless `truecolor`
+ * is set in the config.
+ */
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::env;
+use tui::style::{Color, Modifier, Style};
+
+/// Styles of highlighted code in Markdown.
+#[derive(Clone, Copy, Debug)]
+pub struct SyntaxStyles {
+    /// Code spans, and code blocks in unknown languages
+    pub code: Style,
+    pub keyword: Style,
+    /// Literals and numbers
+    pub literal: Style,
+    pub string: Style,
+    pub comment: Style,
+    pub type_name: Style,
+}
+
+impl SyntaxStyles {
+    fn dark() -> Self {
+        SyntaxStyles {
+            code: fg(Color::Yellow),
+            keyword: fg(Color::Magenta),
+            literal: fg(Color::Cyan),
+            string: fg(Color::Green),
+            comment: style(Color::DarkGray, Color::Reset, Modifier::ITALIC),
+            type_name: fg(Color::Yellow),
+        }
+    }
+
+    fn light() -> Self {
+        SyntaxStyles {
+            code: fg(Color::Blue),
+            keyword: fg(Color::Magenta),
+            literal: fg(Color::Blue),
+            string: fg(Color::Green),
+            comment: style(Color::DarkGray, Color::Reset, Modifier::ITALIC),
+            type_name: fg(DARK_YELLOW),
+        }
+    }
+
+    fn mono() -> Self {
+        SyntaxStyles {
+            code: modifier(Modifier::ITALIC),
+            keyword: modifier(Modifier::BOLD),
+            literal: Style::default(),
+            string: modifier(Modifier::ITALIC),
+            comment: modifier(Modifier::DIM | Modifier::ITALIC),
+            type_name: Style::default(),
+        }
+    }
+}
+
+impl Default for SyntaxStyles {
+    fn default() -> Self {
+        SyntaxStyles::dark()
+    }
+}
+
+/// A readable yellow on light backgrounds
+const DARK_YELLOW: Color = Color::Rgb(181, 137, 0);
+
+/// Styles of every part of the interface.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(try_from = "ThemeConfig", into = "ThemeConfig")]
+pub struct Theme {
+    config: ThemeConfig,
+    truecolor: bool,
+    /// Background of modals
+    pub modal: Style,
+    pub button: Style,
+    pub error: Style,
+    /// Number of assets below the question
+    pub assets: Style,
+    pub line_number: Style,
+    pub search_match: Style,
+    /// Options turned on
+    pub highlight: Style,
+    pub current_path: Style,
+    /// Keys in usage hints
+    pub hint: Style,
+    pub input: Style,
+    /// The chosen answer
+    pub selected: Style,
+    pub cursor: Style,
+    pub correct: Style,
+    pub partial: Style,
+    pub wrong: Style,
+    pub pending: Style,
+    pub progress: Style,
+    pub jumpbox: Style,
+    /// Items in the sidebar
+    pub item_pending: Style,
+    pub item_current: Style,
+    pub item_done: Style,
+    pub item_correct: Style,
+    pub item_partial: Style,
+    pub item_wrong: Style,
+    pub item_flagged: Style,
+    /// The current item while answers are shown, drawn in the colour of its
+    /// result on the background of this style
+    pub item_reviewed: Style,
+    pub syntax: SyntaxStyles,
+}
+
+impl Theme {
+    fn dark() -> Self {
+        Theme {
+            config: ThemeConfig::default(),
+            truecolor: true,
+            modal: bg(Color::Gray),
+            button: style(Color::White, Color::Magenta, Modifier::empty()),
+            error: style(Color::Red, Color::Gray, Modifier::empty()),
+            assets: fg(Color::Red),
+            line_number: style(Color::DarkGray, Color::Gray, Modifier::empty()),
+            search_match: style(Color::Black, Color::Yellow, Modifier::empty()),
+            highlight: fg(Color::Magenta),
+            current_path: style(Color::Green, Color::Reset, Modifier::BOLD),
+            hint: modifier(Modifier::UNDERLINED),
+            input: style(Color::Black, Color::Gray, Modifier::empty()),
+            selected: style(Color::White, Color::Magenta, Modifier::BOLD),
+            cursor: style(Color::White, Color::Magenta, Modifier::empty()),
+            correct: style(Color::Green, Color::Reset, Modifier::BOLD),
+            partial: style(Color::Yellow, Color::Reset, Modifier::BOLD),
+            wrong: style(Color::Red, Color::Reset, Modifier::BOLD),
+            pending: style(Color::Gray, Color::Reset, Modifier::BOLD),
+            progress: style(
+                Color::Rgb(147, 161, 161),
+                Color::Rgb(238, 232, 213),
+                Modifier::empty(),
+            ),
+            jumpbox: modifier(Modifier::UNDERLINED),
+            item_pending: style(Color::Black, Color::Gray, Modifier::empty()),
+            item_current: style(Color::Gray, Color::Magenta, Modifier::BOLD),
+            item_done: style(Color::White, Color::Blue, Modifier::empty()),
+            item_correct: style(Color::White, Color::Green, Modifier::empty()),
+            item_partial: style(Color::Black, Color::Yellow, Modifier::empty()),
+            item_wrong: style(Color::White, Color::Red, Modifier::empty()),
+            item_flagged: style(Color::Black, Color::Cyan, Modifier::UNDERLINED),
+            item_reviewed: style(Color::Reset, Color::Rgb(209, 162, 226), Modifier::BOLD),
+            syntax: SyntaxStyles::dark(),
+        }
+    }
+
+    fn light() -> Self {
+        Theme {
+            partial: style(DARK_YELLOW, Color::Reset, Modifier::BOLD),
+            pending: style(Color::DarkGray, Color::Reset, Modifier::BOLD),
+            item_current: style(Color::White, Color::Magenta, Modifier::BOLD),
+            progress: style(
+                Color::Rgb(88, 110, 117),
+                Color::Rgb(238, 232, 213),
+                Modifier::empty(),
+            ),
+            syntax: SyntaxStyles::light(),
+            ..Theme::dark()
+        }
+    }
+
+    fn mono() -> Self {
+        Theme {
+            config: ThemeConfig::default(),
+            truecolor: true,
+            modal: modifier(Modifier::REVERSED),
+            button: modifier(Modifier::BOLD),
+            error: modifier(Modifier::REVERSED | Modifier::BOLD),
+            assets: modifier(Modifier::BOLD),
+            line_number: modifier(Modifier::REVERSED | Modifier::DIM),
+            search_match: modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            highlight: modifier(Modifier::BOLD | Modifier::REVERSED),
+            current_path: modifier(Modifier::BOLD),
+            hint: modifier(Modifier::UNDERLINED),
+            input: modifier(Modifier::UNDERLINED),
+            selected: modifier(Modifier::REVERSED | Modifier::BOLD),
+            cursor: modifier(Modifier::REVERSED),
+            correct: modifier(Modifier::BOLD),
+            partial: modifier(Modifier::ITALIC),
+            wrong: modifier(Modifier::REVERSED),
+            pending: modifier(Modifier::DIM),
+            progress: style(Color::White, Color::Black, Modifier::empty()),
+            jumpbox: modifier(Modifier::UNDERLINED),
+            item_pending: modifier(Modifier::DIM),
+            item_current: modifier(Modifier::REVERSED | Modifier::BOLD),
+            item_done: modifier(Modifier::BOLD),
+            item_correct: modifier(Modifier::BOLD),
+            item_partial: modifier(Modifier::ITALIC),
+            item_wrong: modifier(Modifier::REVERSED),
+            item_flagged: modifier(Modifier::UNDERLINED),
+            item_reviewed: modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            syntax: SyntaxStyles::mono(),
+        }
+    }
+
+    /// Returns whether RGB colours are drawn as they are.
+    pub fn truecolor(&self) -> bool {
+        self.truecolor
+    }
+
+    /// Styles by their names in the config.
+    fn styles_mut(&mut self) -> Vec<(&'static str, &mut Style)> {
+        vec![
+            ("modal", &mut self.modal),
+            ("button", &mut self.button),
+            ("error", &mut self.error),
+            ("assets", &mut self.assets),
+            ("line_number", &mut self.line_number),
+            ("search_match", &mut self.search_match),
+            ("highlight", &mut self.highlight),
+            ("current_path", &mut self.current_path),
+            ("hint", &mut self.hint),
+            ("input", &mut self.input),
+            ("selected", &mut self.selected),
+            ("cursor", &mut self.cursor),
+            ("correct", &mut self.correct),
+            ("partial", &mut self.partial),
+            ("wrong", &mut self.wrong),
+            ("pending", &mut self.pending),
+            ("progress", &mut self.progress),
+            ("jumpbox", &mut self.jumpbox),
+            ("item_pending", &mut self.item_pending),
+            ("item_current", &mut self.item_current),
+            ("item_done", &mut self.item_done),
+            ("item_correct", &mut self.item_correct),
+            ("item_partial", &mut self.item_partial),
+            ("item_wrong", &mut self.item_wrong),
+            ("item_flagged", &mut self.item_flagged),
+            ("item_reviewed", &mut self.item_reviewed),
+            ("code", &mut self.syntax.code),
+            ("keyword", &mut self.syntax.keyword),
+            ("literal", &mut self.syntax.literal),
+            ("string", &mut self.syntax.string),
+            ("comment", &mut self.syntax.comment),
+            ("type", &mut self.syntax.type_name),
+        ]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::try_from(ThemeConfig::default()).unwrap()
+    }
+}
+
+/// The `theme` section of the config.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+struct ThemeConfig {
+    name: String,
+    styles: BTreeMap<String, StyleConfig>,
+    /// Detected from the terminal when not given
+    truecolor: Option<bool>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        ThemeConfig {
+            name: "dark".to_owned(),
+            styles: BTreeMap::new(),
+            truecolor: None,
+        }
+    }
+}
+
+/// Overrides of a style. Modifiers replace those of the style.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct StyleConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modifiers: Option<Vec<String>>,
+}
+
+impl StyleConfig {
+    fn apply(&self, style: &mut Style) -> Result<(), String> {
+        if let Some(text) = &self.fg {
+            style.fg = parse_color(text).ok_or_else(|| format!("Invalid colour \"{}\"", text))?;
+        }
+        if let Some(text) = &self.bg {
+            style.bg = parse_color(text).ok_or_else(|| format!("Invalid colour \"{}\"", text))?;
+        }
+        if let Some(modifiers) = &self.modifiers {
+            style.modifier = Modifier::empty();
+            for text in modifiers {
+                style.modifier |=
+                    parse_modifier(text).ok_or_else(|| format!("Invalid modifier \"{}\"", text))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<ThemeConfig> for Theme {
+    type Error = String;
+
+    fn try_from(config: ThemeConfig) -> Result<Self, Self::Error> {
+        let mut theme = match config.name.as_str() {
+            "dark" => Theme::dark(),
+            "light" => Theme::light(),
+            "mono" => Theme::mono(),
+            name => return Err(format!("Unknown theme: {}", name)),
+        };
+        for (name, overrides) in &config.styles {
+            let mut styles = theme.styles_mut();
+            let style = styles
+                .iter_mut()
+                .find(|(style_name, _)| style_name == name)
+                .map(|(_, style)| style)
+                .ok_or_else(|| format!("Unknown style in theme: {}", name))?;
+            overrides
+                .apply(style)
+                .map_err(|err| format!("{} for {}", err, name))?;
+        }
+
+        theme.truecolor = config.truecolor.unwrap_or_else(detect_truecolor);
+        if !theme.truecolor {
+            for (_, style) in theme.styles_mut() {
+                style.fg = to_indexed(style.fg);
+                style.bg = to_indexed(style.bg);
+            }
+        }
+        theme.config = config;
+        Ok(theme)
+    }
+}
+
+impl From<Theme> for ThemeConfig {
+    fn from(theme: Theme) -> Self {
+        theme.config
+    }
+}
+
+const COLOR_NAMES: &[(&str, Color)] = &[
+    ("Reset", Color::Reset),
+    ("Black", Color::Black),
+    ("Red", Color::Red),
+    ("Green", Color::Green),
+    ("Yellow", Color::Yellow),
+    ("Blue", Color::Blue),
+    ("Magenta", Color::Magenta),
+    ("Cyan", Color::Cyan),
+    ("Gray", Color::Gray),
+    ("DarkGray", Color::DarkGray),
+    ("LightRed", Color::LightRed),
+    ("LightGreen", Color::LightGreen),
+    ("LightYellow", Color::LightYellow),
+    ("LightBlue", Color::LightBlue),
+    ("LightMagenta", Color::LightMagenta),
+    ("LightCyan", Color::LightCyan),
+    ("White", Color::White),
+];
+
+const MODIFIER_NAMES: &[(&str, Modifier)] = &[
+    ("bold", Modifier::BOLD),
+    ("dim", Modifier::DIM),
+    ("italic", Modifier::ITALIC),
+    ("underlined", Modifier::UNDERLINED),
+    ("slow_blink", Modifier::SLOW_BLINK),
+    ("rapid_blink", Modifier::RAPID_BLINK),
+    ("reversed", Modifier::REVERSED),
+    ("hidden", Modifier::HIDDEN),
+    ("crossed_out", Modifier::CROSSED_OUT),
+];
+
+/// Parses a colour name, `#rrggbb`, or the number of an indexed colour.
+fn parse_color(text: &str) -> Option<Color> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |pos: usize| u8::from_str_radix(&hex[pos..pos + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    if let Ok(index) = text.parse() {
+        return Some(Color::Indexed(index));
+    }
+    COLOR_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(text))
+        .map(|(_, color)| *color)
+}
+
+fn parse_modifier(text: &str) -> Option<Modifier> {
+    MODIFIER_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(text))
+        .map(|(_, modifier)| *modifier)
+}
+
+fn detect_truecolor() -> bool {
+    env::var("COLORTERM")
+        .map(|value| value == "truecolor" || value == "24bit")
+        .unwrap_or(false)
+}
+
+/// Levels of each channel in the 6x6x6 colour cube of indexed colours.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Replaces an RGB colour with the closest indexed colour, from the colour
+/// cube or the greys.
+pub fn to_indexed(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        _ => return color,
+    };
+    let distance = |(r1, g1, b1): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, r1) + d(g, g1) + d(b, b1)
+    };
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|pos| (CUBE_LEVELS[*pos] as i32 - value as i32).abs())
+            .unwrap()
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    // Greys run from 8 to 238 in steps of 10
+    let average = (r as usize + g as usize + b as usize) / 3;
+    let grey_pos = (average.saturating_sub(3) / 10).min(23);
+    let grey_level = (8 + grey_pos * 10) as u8;
+
+    Color::Indexed(
+        match distance((grey_level, grey_level, grey_level)) < distance(cube) {
+            true => 232 + grey_pos as u8,
+            false => (16 + ri * 36 + gi * 6 + bi) as u8,
+        },
+    )
+}
+
+fn style(fg: Color, bg: Color, modifier: Modifier) -> Style {
+    Style { fg, bg, modifier }
+}
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+fn bg(color: Color) -> Style {
+    Style::default().bg(color)
+}
+
+fn modifier(modifier: Modifier) -> Style {
+    Style::default().modifier(modifier)
+}
@@ -1,8 +1,11 @@
+use crate::markdown;
+use crate::theme::SyntaxStyles;
 use tui::buffer::Buffer;
 use tui::layout::Rect;
 use tui::style::Style;
 use tui::symbols::line;
 use tui::widgets::{Block, Borders, Paragraph, Text, Widget};
+use unicode_width::UnicodeWidthStr;
 
 pub struct ToggleButtonState {
     pub text: String,
@@ -24,6 +27,11 @@ pub struct ToggleButtons<'a> {
     wrapper: [&'a str; 2],
     /// Marker string
     marker: &'a str,
+    /// Styles of highlighted code
+    syntax: SyntaxStyles,
+    /// Search highlighted in the texts, and its style
+    search: &'a str,
+    search_style: Style,
 }
 
 impl<'a> ToggleButtons<'a> {
@@ -37,6 +45,9 @@ impl<'a> ToggleButtons<'a> {
             border_style: Default::default(),
             wrapper: ["[", "]"],
             marker: "x",
+            syntax: SyntaxStyles::default(),
+            search: "",
+            search_style: Default::default(),
         }
     }
 
@@ -44,6 +55,114 @@ impl<'a> ToggleButtons<'a> {
         self.wrapper = wrapper;
         self
     }
+
+    pub fn syntax(mut self, syntax: SyntaxStyles) -> ToggleButtons<'a> {
+        self.syntax = syntax;
+        self
+    }
+
+    pub fn highlight<'b: 'a>(mut self, search: &'b str, style: Style) -> ToggleButtons<'a> {
+        self.search = search;
+        self.search_style = style;
+        self
+    }
+
+    /// Returns the rows taken by each button when rendered in `area`, for
+    /// hit-testing mouse clicks. Buttons below the area are left out.
+    pub fn button_areas(&self, area: Rect) -> Vec<Rect> {
+        let area = match self.block {
+            Some(ref b) => b.inner(area),
+            None => area,
+        };
+        if area.width < 2 || area.height < 2 {
+            return vec![];
+        }
+        let text_area = self.text_area(area);
+
+        let mut y = text_area.top();
+        let mut areas = vec![];
+        for (num_lines, _) in self.button_texts(text_area.width) {
+            if y >= text_area.bottom() {
+                break;
+            }
+            let height = num_lines.min(text_area.bottom() - y);
+            areas.push(Rect::new(text_area.left(), y, text_area.width, height));
+            y += num_lines;
+        }
+        areas
+    }
+
+    /// The area of the texts inside the borders
+    fn text_area(&self, textbox_area: Rect) -> Rect {
+        let text_position_x = if self.borders.contains(Borders::LEFT) {
+            textbox_area.left() + 1
+        } else {
+            textbox_area.left()
+        };
+        let text_position_y = if self.borders.contains(Borders::TOP) {
+            textbox_area.top() + 1
+        } else {
+            textbox_area.top()
+        };
+
+        let textbox_width = textbox_area.width
+            - u16::from(self.borders.contains(Borders::LEFT))
+            - u16::from(self.borders.contains(Borders::RIGHT));
+
+        let textbox_height = textbox_area.height
+            - u16::from(self.borders.contains(Borders::TOP))
+            - u16::from(self.borders.contains(Borders::BOTTOM));
+
+        Rect::new(
+            text_position_x,
+            text_position_y,
+            textbox_width,
+            textbox_height,
+        )
+    }
+
+    /// Texts are rendered as Markdown, with wrapped lines aligned after
+    /// the checkbox. Returns the number of lines and the texts of each button.
+    fn button_texts(&self, textbox_width: u16) -> Vec<(u16, Vec<Text<'static>>)> {
+        self.state
+            .iter()
+            .enumerate()
+            .map(|(index, button_state)| {
+                let ToggleButtonState { text, selected } = button_state;
+                let selection_alphabetic = (b'A' + index as u8) as char;
+                let [wl, wr] = self.wrapper;
+                let (checkbox, style) = match selected {
+                    true => (format!("{}{}{}", wl, self.marker, wr), self.selected_style),
+                    false => (format!("{} {}", wl, wr), self.style),
+                };
+                let prefix = format!("{}{}. ", checkbox, selection_alphabetic);
+                let indent = prefix.width();
+                let width = (textbox_width as usize).saturating_sub(indent);
+                let lines = markdown::render_lines(text, width, style, &self.syntax);
+                let num_lines = lines.len() as u16;
+                let mut texts: Vec<Text<'static>> = vec![];
+                lines
+                    .into_iter()
+                    .enumerate()
+                    .for_each(|(line_index, line)| {
+                        let line_prefix = match line_index {
+                            0 => prefix.clone(),
+                            _ => " ".repeat(indent),
+                        };
+                        texts.push(Text::styled(line_prefix, style));
+                        texts.extend(markdown::highlight(
+                            line.into_iter()
+                                .map(|(text, style)| Text::styled(text, style))
+                                .collect(),
+                            self.search,
+                            self.search_style,
+                        ));
+                        texts.push(Text::styled("\n", style));
+                    });
+                (num_lines, texts)
+            })
+            .collect()
+    }
 }
 
 impl<'a> Widget for ToggleButtons<'a> {
@@ -115,57 +234,12 @@ impl<'a> Widget for ToggleButtons<'a> {
                 .set_style(self.border_style);
         }
 
-        let text_position_x = if self.borders.contains(Borders::LEFT) {
-            textbox_area.left() + 1
-        } else {
-            textbox_area.left()
-        };
-        let text_position_y = if self.borders.contains(Borders::TOP) {
-            textbox_area.top() + 1
-        } else {
-            textbox_area.top()
-        };
-
-        let textbox_width = textbox_area.width
-            - u16::from(self.borders.contains(Borders::LEFT))
-            - u16::from(self.borders.contains(Borders::RIGHT));
-
-        let textbox_height = textbox_area.height
-            - u16::from(self.borders.contains(Borders::TOP))
-            - u16::from(self.borders.contains(Borders::BOTTOM));
-
+        let para_rect = self.text_area(textbox_area);
         let texts: Vec<Text> = self
-            .state
-            .iter()
-            .enumerate()
-            .map(|(index, button_state)| {
-                let ToggleButtonState { text, selected } = button_state;
-                let selection_alphabetic = ('A' as u8 + index as u8) as char;
-                let [wl, wr] = self.wrapper;
-                if *selected {
-                    let checkbox = format!("{}{}{}", wl, self.marker, wr);
-                    Text::styled(
-                        format!("{}{}. {}\n", checkbox, selection_alphabetic, text),
-                        self.selected_style,
-                    )
-                } else {
-                    let checkbox = format!("{} {}", wl, wr);
-                    Text::styled(
-                        format!("{}{}. {}\n", checkbox, selection_alphabetic, text),
-                        self.style,
-                    )
-                }
-            })
+            .button_texts(para_rect.width)
+            .into_iter()
+            .flat_map(|(_, texts)| texts)
             .collect();
-
-        let para_rect = Rect::new(
-            text_position_x,
-            text_position_y,
-            textbox_width,
-            textbox_height,
-        );
-        Paragraph::new(texts.iter())
-            .wrap(true)
-            .render(para_rect, buf);
+        Paragraph::new(texts.iter()).render(para_rect, buf);
     }
 }
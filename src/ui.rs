@@ -8,34 +8,217 @@
  *   - HomeWidget
  *   - ExamWidget
  *     - JumpBarWidget
+ *     - ExamSearchWidget
  *     - ExamItemsWidget
  *     - ItemWidget
  *       - QuestionWidget
+ *       - CardWidget
+ *       - TextAnswerWidget
+ *       - TrueFalseWidget
+ *       - OrderingWidget
+ *       - MatchingWidget
+ *   - ResultsWidget
  *   - SaveModalWidget
+ *   - AssetsModalWidget
+ *   - ErrorModalWidget
+ *   - ConfirmModalWidget
  */
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::sync::mpsc;
 use tui::backend::Backend;
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use tui::style::{Color, Modifier, Style};
-use tui::widgets::{Clear, Block, Borders, Gauge, List, Paragraph, Text};
+use tui::style::{Modifier, Style};
+use tui::widgets::{Block, Borders, Clear, Gauge, List, ListState, Paragraph, Text};
 use tui::Frame;
 
 use crate::app::*;
 use crate::event::*;
+use crate::keymap::{Action, Keymap, Scope};
+use crate::markdown;
+use crate::mouse::{MouseAction, MouseTarget};
+use crate::preview::ImagePreview;
+use crate::report::*;
+use crate::theme::Theme;
 use crate::toggle_buttons::*;
 
-const BG_STYLE: Style = Style {
-    fg: Color::Reset,
-    bg: Color::Gray,
-    modifier: Modifier::empty(),
-};
-const BUTTON_STYLE: Style = Style {
-    fg: Color::White,
-    bg: Color::Magenta,
-    modifier: Modifier::empty(),
-};
 const BTN_WIDTH: u16 = 8;
 
+/// Returns the title of the current item: its kind, number and flag, and the
+/// points earned once the answer is shown.
+fn item_title(kind: &str, exam: &Exam, display: &DoExamDisplay) -> String {
+    let index = display.question_index;
+    let item = exam.question_at(index);
+    let title = format!(
+        "{} ({}/{}){}",
+        kind,
+        exam.display_number(index),
+        exam.num_questions(),
+        match item.map(|item| item.is_flagged()) {
+            Some(true) => " [flagged]",
+            _ => "",
+        }
+    );
+    let points = item.and_then(|item| Some((item.get_score(exam.scoring)?, item.max_points())));
+    match (display.display_answer, points) {
+        (true, Some((points, max_points))) => format!(
+            "{} [{}/{} pts]",
+            title,
+            format_points(points),
+            format_points(max_points)
+        ),
+        _ => title,
+    }
+}
+
+/// Returns the scopes handling the keys of `item`, along with the exam.
+fn item_scopes(item: &Item) -> &'static [Scope] {
+    match item {
+        Item::Question(_) => &[Scope::Global, Scope::Exam, Scope::Item],
+        Item::Card(_) => &[Scope::Global, Scope::Exam, Scope::Item, Scope::Card],
+        Item::Text(_) | Item::Numeric(_) => &[Scope::Global, Scope::Exam, Scope::Item, Scope::Text],
+        Item::TrueFalse(_) => &[Scope::Global, Scope::Exam, Scope::Item, Scope::TrueFalse],
+        Item::Ordering(_) => &[Scope::Global, Scope::Exam, Scope::Ordering],
+        Item::Matching(_) => &[Scope::Global, Scope::Exam, Scope::Matching],
+    }
+}
+
+/// Returns the hint of the letters typed to choose one of `num_labels`
+/// labels, e.g. `a-e`. Letters bound to actions of `scopes` are typed with
+/// Alt instead, and listed after the range, e.g. `a-p, Alt-j/k/n/p`.
+fn label_keys_hint(keymap: &Keymap, scopes: &[Scope], num_labels: usize) -> String {
+    let num_labels = num_labels.clamp(1, SelectionFlags::MAX_SELECTIONS);
+    let last = (b'a' + num_labels as u8 - 1) as char;
+    let range = match last {
+        'a' => "a".to_owned(),
+        _ => format!("a-{}", last),
+    };
+    let bound: Vec<String> = keymap
+        .bound_chars(scopes, 'a'..=last)
+        .iter()
+        .map(char::to_string)
+        .collect();
+    match bound.is_empty() {
+        true => range,
+        false => format!("{}, Alt-{}", range, bound.join("/")),
+    }
+}
+
+/// Scrolls the question of the current item by `lines`.
+fn scroll_question(exam: &Exam, lines: i16, tx: &mpsc::Sender<Messages>) {
+    let pos = exam.display.question_scroll_pos;
+    let next_pos = match lines < 0 {
+        true => pos.saturating_sub(lines.unsigned_abs()),
+        false => pos.saturating_add(lines as u16),
+    };
+    tx.send(Messages::ScrollQuestion(next_pos)).unwrap();
+}
+
+/// Renders the text of an item as Markdown inside a bordered block of
+/// `width`, followed by the number of its assets. Matches of `search` are
+/// highlighted.
+fn question_texts(
+    text: &str,
+    num_assets: usize,
+    width: u16,
+    theme: &Theme,
+    keymap: &Keymap,
+    search: &str,
+) -> Vec<Text<'static>> {
+    let mut texts = markdown::highlight(
+        markdown::render(text, width.saturating_sub(2), &theme.syntax),
+        search,
+        theme.search_match,
+    );
+    if num_assets > 0 {
+        let assets_key = keymap.label(Scope::Exam, Action::OpenAssets);
+        let (before, hotkey, after) = hotkey_parts("Assets", &assets_key);
+        texts.push(Text::styled(format!("\n[{}", before), theme.assets));
+        texts.push(Text::styled(
+            hotkey,
+            theme
+                .assets
+                .modifier(theme.assets.modifier | Modifier::UNDERLINED),
+        ));
+        texts.push(Text::styled(
+            format!("{}: {}]", after, num_assets),
+            theme.assets,
+        ));
+    }
+    texts
+}
+
+/// Splits `name` around the letter typed as `key`, to be underlined. The
+/// first letter of a word is preferred, e.g. `SAVE |A|S` for `a`. Keys that
+/// are not a letter of the name are put in front of it, e.g. `|Esc| CLOSE`.
+fn hotkey_parts(name: &str, key: &str) -> (String, String, String) {
+    let mut chars = key.chars();
+    let position = match (chars.next(), chars.next()) {
+        (Some(c), None) => name
+            .char_indices()
+            .filter(|(_, letter)| letter.to_lowercase().eq(c.to_lowercase()))
+            .map(|(i, _)| i)
+            .min_by_key(|&i| !(i == 0 || name[..i].ends_with(' '))),
+        _ => None,
+    };
+    match position {
+        Some(i) => {
+            let end = i + name[i..].chars().next().unwrap().len_utf8();
+            (
+                name[..i].to_owned(),
+                name[i..end].to_owned(),
+                name[end..].to_owned(),
+            )
+        }
+        None => (String::new(), key.to_owned(), format!(" {}", name)),
+    }
+}
+
+/// Returns the texts of a row of buttons, given by their names and keys, laid
+/// out by `ModalRect::btn_pad`.
+fn btn_group(buttons: &[(&str, String)], btn_pad: u16, theme: &Theme) -> Vec<Text<'static>> {
+    let pad_text = || Text::raw(" ".repeat(btn_pad as usize));
+    let mut texts = vec![pad_text()];
+    for (name, key) in buttons {
+        texts.extend(btn_texts(name, key, theme));
+        texts.push(pad_text());
+    }
+    texts
+}
+
+/// Returns the texts of a button named `name`, centered in `BTN_WIDTH`, with
+/// the key of its action underlined.
+fn btn_texts(name: &str, key: &str, theme: &Theme) -> Vec<Text<'static>> {
+    let width = BTN_WIDTH as usize;
+    let (before, hotkey, after) = hotkey_parts(name, key);
+    // Labels too long for the button are cut from the end
+    let before: String = before.chars().take(width).collect();
+    let hotkey: String = hotkey
+        .chars()
+        .take(width - before.chars().count())
+        .collect();
+    let after: String = after
+        .chars()
+        .take(width - before.chars().count() - hotkey.chars().count())
+        .collect();
+    let len = before.chars().count() + hotkey.chars().count() + after.chars().count();
+    // Odd paddings lean to the left, as in ` PREVIEW`
+    let right = (width - len) / 2;
+    vec![
+        Text::styled(
+            format!("{}{}", " ".repeat(width - len - right), before),
+            theme.button,
+        ),
+        Text::styled(
+            hotkey,
+            theme
+                .button
+                .modifier(theme.button.modifier | Modifier::UNDERLINED),
+        ),
+        Text::styled(format!("{}{}", after, " ".repeat(right)), theme.button),
+    ]
+}
+
+#[derive(Clone, Copy)]
 struct ModalRect {
     content: Rect,
     px: u16,
@@ -71,12 +254,31 @@ impl ModalRect {
         Self { content, px, py }
     }
 
+    /// A modal that leaves little space around it.
+    fn wide(content: Rect) -> Self {
+        let px = content.width / 10;
+        let py = if content.height > 10 { 1 } else { 0 };
+
+        Self { content, px, py }
+    }
+
     fn btn_pad(&self, num_btns: u16) -> u16 {
         let inner_width = self.content.width - self.px * 2 - 2;
 
         // pad-around
         (inner_width - BTN_WIDTH * num_btns) / (num_btns + 1)
     }
+
+    /// The areas of the buttons laid out by `btn_pad` in the bordered `row`.
+    fn btn_areas(&self, row: Rect, num_btns: u16) -> Vec<Rect> {
+        let btn_pad = self.btn_pad(num_btns);
+        (0..num_btns)
+            .map(|index| {
+                let x = row.x + 1 + btn_pad * (index + 1) + BTN_WIDTH * index;
+                Rect::new(x, row.y, BTN_WIDTH, 1)
+            })
+            .collect()
+    }
 }
 
 pub struct AppWidget<'a> {
@@ -89,10 +291,13 @@ impl<'a> AppWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        self.app.mouse_areas.clear();
+
         // The main view
         match &self.app.route {
             AppRoute::Home => HomeWidget::new(self.app).draw(frame, content),
             AppRoute::DoExam => ExamWidget::new(self.app).draw(frame, content),
+            AppRoute::Results => ResultsWidget::new(self.app).draw(frame, content),
         };
 
         // Overlay modals
@@ -100,27 +305,61 @@ impl<'a> AppWidget<'a> {
             SaveModalState::ShowSave => {
                 SaveModalWidget::new(self.app).draw(frame, content);
             }
+            SaveModalState::ShowSaveAs(_) | SaveModalState::ShowOverwrite(_) => {
+                SaveModalWidget::new(self.app).draw(frame, content);
+            }
             SaveModalState::ShowQuit(_) => {
                 SaveModalWidget::new(self.app).draw(frame, content);
             }
             _ => {}
         }
         match self.app.modal.assets_modal_state {
-            AssetsModalState::Show(_) => {
+            AssetsModalState::Show(..) | AssetsModalState::View(..) => {
                 AssetsModalWidget::new(self.app).draw(frame, content);
             }
             _ => {}
         }
+        if let ConfirmModalState::Show(..) = self.app.modal.confirm_modal_state {
+            ConfirmModalWidget::new(self.app).draw(frame, content);
+        }
+        if let ErrorModalState::Show(_) = self.app.modal.error_modal_state {
+            ErrorModalWidget::new(self.app).draw(frame, content);
+        }
     }
 
     pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
         // Propagation
+        let keymap = &state.config.keymap;
         match &event {
-            Messages::Input(key!('Q')) => {
+            Messages::Input(keyevent)
+                if keymap.action(Scope::Global, keyevent) == Some(Action::QuitProgram) =>
+            {
+                // Typed as text in the exam finder
+                if state.home.finder.is_some() {
+                    if let AppRoute::Home = state.route {
+                        return HomeWidget::propagate(state, event, tx);
+                    }
+                };
                 // Do not ask when quit from home
                 if let AppRoute::Home = state.route {
                     return None;
                 };
+                // Typed as text in the "Save As" input
+                if let SaveModalState::ShowSaveAs(_) = state.modal.save_modal_state {
+                    return SaveModalWidget::propagate(state, event, tx);
+                };
+                // Typed as text in the search input
+                if let AssetsModalState::View(_, text_view) = &state.modal.assets_modal_state {
+                    if text_view.search_input.is_some() {
+                        return AssetsModalWidget::propagate(state, event, tx);
+                    }
+                };
+                // Typed as text in the answer or search input
+                if let Some(exam) = &state.exam {
+                    if exam.display.answer_input.is_some() || exam.display.search_input.is_some() {
+                        return ExamWidget::propagate(state, event, tx);
+                    }
+                };
 
                 state.exam.as_ref().map(|exam| match exam.unsaved_changes {
                     true => tx
@@ -134,6 +373,14 @@ impl<'a> AppWidget<'a> {
             }
             _ => Some(event),
         }
+        .and_then(|event| match &state.modal.error_modal_state {
+            ErrorModalState::Hidden => Some(event),
+            _ => ErrorModalWidget::propagate(state, event, tx.clone()),
+        })
+        .and_then(|event| match &state.modal.confirm_modal_state {
+            ConfirmModalState::Hidden => Some(event),
+            _ => ConfirmModalWidget::propagate(state, event, tx.clone()),
+        })
         .and_then(|event| match &state.modal.save_modal_state {
             SaveModalState::Hidden => Some(event),
             _ => SaveModalWidget::propagate(state, event, tx.clone()),
@@ -145,6 +392,7 @@ impl<'a> AppWidget<'a> {
         .and_then(|event| match state.route {
             AppRoute::Home => HomeWidget::propagate(state, event, tx),
             AppRoute::DoExam => ExamWidget::propagate(state, event, tx),
+            AppRoute::Results => ResultsWidget::propagate(state, event, tx),
         })
     }
 }
@@ -159,11 +407,14 @@ impl<'a> SaveModalWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
         let modal_state = &self.app.modal.save_modal_state;
 
         let num_btns = match modal_state {
             SaveModalState::ShowQuit(_) => 3,
-            SaveModalState::ShowSave => 2,
+            SaveModalState::ShowSave => 3,
+            SaveModalState::ShowSaveAs(_) => 2,
+            SaveModalState::ShowOverwrite(_) => 2,
             _ => unreachable!(),
         };
 
@@ -175,18 +426,36 @@ impl<'a> SaveModalWidget<'a> {
             .constraints([Constraint::Min(6), Constraint::Length(2)].as_ref())
             .split(content.into());
 
-        let filename = self.app.home.get_selected_path().unwrap();
-        let description_text = match modal_state {
+        let filename = self
+            .app
+            .home
+            .exam_src
+            .as_ref()
+            .and_then(|filename| filename.to_str())
+            .unwrap_or("???");
+        let description_texts = match modal_state {
             SaveModalState::ShowSave => {
-                format!("Save changes to \"{}\"?", filename.to_str().unwrap())
+                vec![Text::raw(format!("Save changes to \"{}\"?", filename))]
             }
-            SaveModalState::ShowQuit(_) => format!(
+            SaveModalState::ShowSaveAs(input) => vec![
+                Text::raw("Save as (.json, .exhaust or .gz):\n\n"),
+                Text::styled(
+                    format!("{}_", input),
+                    theme
+                        .modal
+                        .modifier(theme.modal.modifier | Modifier::UNDERLINED),
+                ),
+            ],
+            SaveModalState::ShowOverwrite(path) => vec![Text::raw(format!(
+                "\"{}\" already exists. Overwrite it?",
+                path.to_str().unwrap_or("???")
+            ))],
+            SaveModalState::ShowQuit(_) => vec![Text::raw(format!(
                 "Save changes to \"{}\" before quit?",
-                filename.to_str().unwrap()
-            ),
+                filename
+            ))],
             _ => unreachable!(),
         };
-        let description_texts = [Text::raw(description_text)];
 
         frame.render_widget(Clear, layout[0].clone());
         frame.render_widget(
@@ -196,41 +465,50 @@ impl<'a> SaveModalWidget<'a> {
                 .block(
                     Block::default()
                         .borders(Borders::ALL - Borders::BOTTOM)
-                        .border_style(BG_STYLE),
+                        .border_style(theme.modal),
                 )
-                .style(BG_STYLE),
+                .style(theme.modal),
             layout[0],
         );
 
-        let pad_text = || Text::raw(" ".repeat(btn_pad as usize));
-
+        let label = |action| self.app.config.keymap.label(Scope::Save, action);
         let btn_group = match modal_state {
-            SaveModalState::ShowSave => vec![
-                pad_text(),
-                Text::styled("   ", BUTTON_STYLE),
-                Text::styled("O", BUTTON_STYLE.modifier(Modifier::UNDERLINED)),
-                Text::styled("K   ", BUTTON_STYLE),
-                pad_text(),
-                Text::styled(" ", BUTTON_STYLE),
-                Text::styled("C", BUTTON_STYLE.modifier(Modifier::UNDERLINED)),
-                Text::styled("ANCEL ", BUTTON_STYLE),
-                pad_text(),
-            ],
-            SaveModalState::ShowQuit(_) => vec![
-                pad_text(),
-                Text::styled("  ", BUTTON_STYLE),
-                Text::styled("Q", BUTTON_STYLE.modifier(Modifier::UNDERLINED)),
-                Text::styled("UIT  ", BUTTON_STYLE),
-                pad_text(),
-                Text::styled("   ", BUTTON_STYLE),
-                Text::styled("O", BUTTON_STYLE.modifier(Modifier::UNDERLINED)),
-                Text::styled("K   ", BUTTON_STYLE),
-                pad_text(),
-                Text::styled(" ", BUTTON_STYLE),
-                Text::styled("C", BUTTON_STYLE.modifier(Modifier::UNDERLINED)),
-                Text::styled("ANCEL ", BUTTON_STYLE),
-                pad_text(),
-            ],
+            SaveModalState::ShowSave => btn_group(
+                &[
+                    ("OK", label(Action::Okay)),
+                    ("SAVE AS", label(Action::SaveAs)),
+                    ("CANCEL", label(Action::Cancel)),
+                ],
+                btn_pad,
+                theme,
+            ),
+            SaveModalState::ShowSaveAs(_) => {
+                let pad_text = || Text::raw(" ".repeat(btn_pad as usize));
+                vec![
+                    pad_text(),
+                    Text::styled("  SAVE  ", theme.button),
+                    pad_text(),
+                    Text::styled(" CANCEL ", theme.button),
+                    pad_text(),
+                ]
+            }
+            SaveModalState::ShowOverwrite(_) => btn_group(
+                &[
+                    ("OK", label(Action::Okay)),
+                    ("CANCEL", label(Action::Cancel)),
+                ],
+                btn_pad,
+                theme,
+            ),
+            SaveModalState::ShowQuit(_) => btn_group(
+                &[
+                    ("QUIT", label(Action::Quit)),
+                    ("OK", label(Action::Okay)),
+                    ("CANCEL", label(Action::Cancel)),
+                ],
+                btn_pad,
+                theme,
+            ),
             _ => unreachable!(),
         };
 
@@ -240,50 +518,127 @@ impl<'a> SaveModalWidget<'a> {
                 .block(
                     Block::default()
                         .borders(Borders::ALL - Borders::TOP)
-                        .border_style(BG_STYLE),
+                        .border_style(theme.modal),
                 )
-                .style(BG_STYLE),
+                .style(theme.modal),
             layout[1],
         );
+
+        let btn_actions = match modal_state {
+            SaveModalState::ShowSave => [Action::Okay, Action::SaveAs, Action::Cancel].as_ref(),
+            SaveModalState::ShowSaveAs(_) => [Action::Submit, Action::Cancel].as_ref(),
+            SaveModalState::ShowOverwrite(_) => [Action::Okay, Action::Cancel].as_ref(),
+            SaveModalState::ShowQuit(_) => [Action::Quit, Action::Okay, Action::Cancel].as_ref(),
+            _ => unreachable!(),
+        };
+        content
+            .btn_areas(layout[1], num_btns)
+            .into_iter()
+            .zip(btn_actions)
+            .for_each(|(area, action)| {
+                self.app.mouse_areas.add(area, MouseTarget::Button(*action))
+            });
     }
 
     pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let keymap = &state.config.keymap;
         let modal_state = &state.modal.save_modal_state;
+        // Buttons are clicked as the actions of their keys
+        let action = match &event {
+            Messages::Input(keyevent) => match modal_state {
+                SaveModalState::ShowSaveAs(_) => keymap.action(Scope::Input, keyevent),
+                _ => keymap.action(Scope::Save, keyevent),
+            },
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::Click(MouseTarget::Button(action))) => Some(action),
+                _ => None,
+            },
+            _ => None,
+        };
         match modal_state {
-            SaveModalState::ShowQuit(quit_action) => match event {
-                Messages::Input(keyevent) => match keyevent {
-                    key!('q') | key!('Q') => {
-                        let action = SaveModalActions::Quit(quit_action.clone());
-                        tx.send(Messages::SaveModalAction(action)).unwrap();
-                    }
-                    key!('o') | key!('O') => {
-                        tx.send(Messages::SaveModalAction(SaveModalActions::Okay))
-                            .and_then(|_| {
-                                let action = SaveModalActions::Quit(quit_action.clone());
-                                tx.send(Messages::SaveModalAction(action))
-                            })
-                            .unwrap();
-                    }
-                    key!('c') | key!('C') | key!(Esc) => {
-                        tx.send(Messages::SaveModalAction(SaveModalActions::Cancel))
-                            .unwrap();
-                    }
-                    _ => {}
-                },
+            SaveModalState::ShowQuit(quit_action) => match action {
+                Some(Action::Quit) => {
+                    let action = SaveModalActions::Quit(quit_action.clone());
+                    tx.send(Messages::SaveModalAction(action)).unwrap();
+                }
+                Some(Action::Okay) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Okay))
+                        .and_then(|_| {
+                            let action = SaveModalActions::Quit(quit_action.clone());
+                            tx.send(Messages::SaveModalAction(action))
+                        })
+                        .unwrap();
+                }
+                Some(Action::Cancel) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Cancel))
+                        .unwrap();
+                }
                 _ => {}
             },
-            SaveModalState::ShowSave => match event {
-                Messages::Input(keyevent) => match keyevent {
-                    key!('o') | key!('O') => {
-                        tx.send(Messages::SaveModalAction(SaveModalActions::Okay))
-                            .unwrap();
-                    }
-                    key!('c') | key!('C') | key!(Esc) => {
-                        tx.send(Messages::SaveModalAction(SaveModalActions::Cancel))
-                            .unwrap();
-                    }
-                    _ => {}
-                },
+            SaveModalState::ShowSave => match action {
+                Some(Action::Okay) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Okay))
+                        .unwrap();
+                }
+                Some(Action::SaveAs) => {
+                    let filename = state
+                        .home
+                        .exam_src
+                        .as_ref()
+                        .and_then(|filename| filename.to_str())
+                        .unwrap_or("")
+                        .to_owned();
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Open(
+                        SaveModalState::ShowSaveAs(filename),
+                    )))
+                    .unwrap();
+                }
+                Some(Action::Cancel) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Cancel))
+                        .unwrap();
+                }
+                _ => {}
+            },
+            SaveModalState::ShowOverwrite(filename) => match action {
+                Some(Action::Okay) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Overwrite(
+                        filename.clone(),
+                    )))
+                    .unwrap();
+                }
+                Some(Action::Cancel) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Cancel))
+                        .unwrap();
+                }
+                _ => {}
+            },
+            SaveModalState::ShowSaveAs(input) => match (action, &event) {
+                (Some(Action::Submit), _) if !input.is_empty() => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::SaveAs(
+                        input.into(),
+                    )))
+                    .unwrap();
+                }
+                (Some(Action::Cancel), _) => {
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Cancel))
+                        .unwrap();
+                }
+                (Some(Action::DeleteChar), _) => {
+                    let mut input = input.clone();
+                    input.pop();
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Open(
+                        SaveModalState::ShowSaveAs(input),
+                    )))
+                    .unwrap();
+                }
+                (_, Messages::Input(key!(Char(c)))) => {
+                    let mut input = input.clone();
+                    input.push(*c);
+                    tx.send(Messages::SaveModalAction(SaveModalActions::Open(
+                        SaveModalState::ShowSaveAs(input),
+                    )))
+                    .unwrap();
+                }
                 _ => {}
             },
             SaveModalState::Hidden => unreachable!(),
@@ -302,183 +657,674 @@ impl<'a> AssetsModalWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
-        let content = ModalRect::new(content);
-        let num_btns = 1;
-        let btn_pad = content.btn_pad(num_btns);
+        let theme = &self.app.config.theme;
+        /// Assets listed above a preview
+        const MAX_PREVIEW_ASSETS: u16 = 5;
 
-        let assets_list_state = match &mut self.app.modal.assets_modal_state {
-            AssetsModalState::Show(list_state) => list_state,
+        let (assets_list_state, preview) = match &self.app.modal.assets_modal_state {
+            AssetsModalState::Show(list_state, preview) => (list_state, preview),
+            AssetsModalState::View(..) => return self.draw_text_view(frame, content),
             AssetsModalState::Hidden => unreachable!(),
         };
+        // Previews take most of the screen
+        let content = match preview {
+            Some(_) => ModalRect::wide(content),
+            None => ModalRect::new(content),
+        };
+        let num_btns = 2;
+        let btn_pad = content.btn_pad(num_btns);
+
         let exam = self.app.exam.as_ref().unwrap();
         let current_item = exam.question_at(exam.display.question_index).unwrap();
         let assets = current_item.get_assets();
 
+        let constraints = match preview {
+            Some(_) => vec![
+                Constraint::Length(2),
+                Constraint::Length((assets.len() as u16).min(MAX_PREVIEW_ASSETS)),
+                Constraint::Min(4),
+                Constraint::Length(2),
+            ],
+            None => vec![
+                Constraint::Length(2),
+                Constraint::Min(4),
+                Constraint::Length(2),
+            ],
+        };
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(content.into());
+        let buttons_rect = layout[layout.len() - 1];
+
+        // Title
+        let title_texts = [Text::styled(
+            "Assets",
+            theme.modal.modifier(theme.modal.modifier | Modifier::BOLD),
+        )];
+        frame.render_widget(Clear, layout[0].clone());
+        frame.render_widget(
+            Paragraph::new(title_texts.iter())
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL - Borders::BOTTOM)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            layout[0],
+        );
+
+        // Assets list. The list scrolls just enough to show the selected
+        // asset, so that clicked rows can be told apart.
+        let selected = assets_list_state.selected();
+        let mut list_state = ListState::default();
+        list_state.select(selected);
+        let list_block = Block::default()
+            .borders(Borders::LEFT | Borders::RIGHT)
+            .border_style(theme.modal);
+        let list_area = list_block.inner(layout[1]);
+        frame.render_widget(Clear, layout[1].clone());
+        frame.render_stateful_widget(
+            List::new(assets.iter().map(|text| Text::raw(text)))
+                .highlight_symbol(">")
+                .highlight_style(theme.modal)
+                .block(list_block)
+                .style(theme.modal),
+            layout[1],
+            &mut list_state,
+        );
+        let offset = (selected.unwrap_or(0) + 1).saturating_sub(list_area.height as usize);
+        (offset..assets.len())
+            .take(list_area.height as usize)
+            .enumerate()
+            .for_each(|(row, index)| {
+                let area = Rect::new(list_area.x, list_area.y + row as u16, list_area.width, 1);
+                self.app.mouse_areas.add(area, MouseTarget::Asset(index));
+            });
+
+        // Preview of the selected image
+        if let Some(preview) = preview {
+            let block = Block::default()
+                .borders(Borders::LEFT | Borders::RIGHT | Borders::TOP)
+                .border_style(theme.modal)
+                .style(theme.modal);
+            frame.render_widget(Clear, layout[2]);
+            match preview {
+                AssetPreview::Loading(_) => {
+                    let texts = [Text::raw("Loading...")];
+                    frame.render_widget(
+                        Paragraph::new(texts.iter()).block(block).style(theme.modal),
+                        layout[2],
+                    );
+                }
+                AssetPreview::Loaded(Ok(image)) => {
+                    frame.render_widget(
+                        ImagePreview::new(image)
+                            .block(block)
+                            .truecolor(theme.truecolor()),
+                        layout[2],
+                    );
+                }
+                AssetPreview::Loaded(Err(message)) => {
+                    let texts = [Text::styled(message.as_str(), theme.error)];
+                    frame.render_widget(
+                        Paragraph::new(texts.iter())
+                            .block(block)
+                            .style(theme.modal)
+                            .wrap(true),
+                        layout[2],
+                    );
+                }
+            }
+        }
+
+        // The buttons
+        let label = |action| self.app.config.keymap.label(Scope::Assets, action);
+        let btn_group = btn_group(
+            &[
+                ("CLOSE", label(Action::Close)),
+                ("PREVIEW", label(Action::TogglePreview)),
+            ],
+            btn_pad,
+            theme,
+        );
+        frame.render_widget(Clear, buttons_rect);
+        frame.render_widget(
+            Paragraph::new(btn_group.iter())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL - Borders::TOP)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            buttons_rect,
+        );
+        content
+            .btn_areas(buttons_rect, num_btns)
+            .into_iter()
+            .zip(&[Action::Close, Action::TogglePreview])
+            .for_each(|(area, action)| {
+                self.app.mouse_areas.add(area, MouseTarget::Button(*action))
+            });
+    }
+
+    fn draw_text_view<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+
+        let text_view = match &mut self.app.modal.assets_modal_state {
+            AssetsModalState::View(_, text_view) => text_view,
+            _ => unreachable!(),
+        };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
                 [
                     Constraint::Length(2),
-                    Constraint::Min(4),
+                    Constraint::Min(1),
                     Constraint::Length(2),
                 ]
                 .as_ref(),
             )
-            .split(content.into());
+            .split(ModalRect::wide(content).into());
+
+        // Scrolling depends on the number of visible lines
+        text_view.height = layout[1].height as usize;
+        text_view.scroll_by(0);
 
         // Title
-        let title_texts = [Text::styled("Assets", BG_STYLE.modifier(Modifier::BOLD))];
-        frame.render_widget(Clear, layout[0].clone());
+        let title_texts = [Text::styled(
+            text_view.title.as_str(),
+            theme.modal.modifier(theme.modal.modifier | Modifier::BOLD),
+        )];
+        frame.render_widget(Clear, layout[0]);
         frame.render_widget(
             Paragraph::new(title_texts.iter())
                 .alignment(Alignment::Center)
                 .block(
                     Block::default()
                         .borders(Borders::ALL - Borders::BOTTOM)
-                        .border_style(BG_STYLE),
+                        .border_style(theme.modal),
                 )
-                .style(BG_STYLE),
+                .style(theme.modal),
             layout[0],
         );
 
-        // Assets list
-        frame.render_widget(Clear, layout[1].clone());
-        frame.render_stateful_widget(
-            List::new(assets.iter().map(|text| Text::raw(text)))
-                .highlight_symbol(">")
-                .highlight_style(BG_STYLE)
+        // Lines with their numbers, and matches of the search highlighted
+        let number_width = text_view.lines.len().to_string().len();
+        let mut texts = vec![];
+        text_view
+            .lines
+            .iter()
+            .enumerate()
+            .skip(text_view.scroll)
+            .take(text_view.height)
+            .for_each(|(index, line)| {
+                let number_style = match text_view.current_match == Some(index) {
+                    true => theme.search_match,
+                    false => theme.line_number,
+                };
+                texts.push(Text::styled(
+                    format!("{:>width$} ", index + 1, width = number_width),
+                    number_style,
+                ));
+                texts.extend(
+                    markdown::split_matches(line, &text_view.search)
+                        .into_iter()
+                        .map(|(text, is_match)| match is_match {
+                            true => Text::styled(text.to_owned(), theme.search_match),
+                            false => Text::styled(text.to_owned(), theme.modal),
+                        }),
+                );
+                texts.push(Text::raw("\n"));
+            });
+        frame.render_widget(Clear, layout[1]);
+        frame.render_widget(
+            Paragraph::new(texts.iter())
                 .block(
                     Block::default()
                         .borders(Borders::LEFT | Borders::RIGHT)
-                        .border_style(BG_STYLE),
+                        .border_style(theme.modal),
                 )
-                .style(BG_STYLE),
+                .style(theme.modal),
             layout[1],
-            assets_list_state,
         );
 
-        // The buttons
-        let pad_text = || Text::raw(" ".repeat(btn_pad as usize));
-        let btn_group = vec![
-            pad_text(),
-            Text::styled(" ", BUTTON_STYLE),
-            Text::styled("C", BUTTON_STYLE.modifier(Modifier::UNDERLINED)),
-            Text::styled("LOSE ", BUTTON_STYLE),
-            pad_text(),
-        ];
-        frame.render_widget(Clear, layout[2].clone());
+        // Search input or usage
+        let keymap = &self.app.config.keymap;
+        let label = |action| keymap.label(Scope::TextView, action);
+        let footer_texts = match &text_view.search_input {
+            Some(input) => vec![
+                Text::styled(
+                    label(Action::Search),
+                    theme.modal.modifier(theme.modal.modifier | Modifier::BOLD),
+                ),
+                Text::styled(input.as_str(), theme.modal),
+                Text::styled(
+                    " ",
+                    theme
+                        .modal
+                        .modifier(theme.modal.modifier | Modifier::REVERSED),
+                ),
+            ],
+            None => vec![
+                Text::styled(
+                    format!(
+                        "{}-{}/{} ",
+                        (text_view.scroll + 1).min(text_view.lines.len()),
+                        (text_view.scroll + text_view.height).min(text_view.lines.len()),
+                        text_view.lines.len()
+                    ),
+                    theme.modal.modifier(theme.modal.modifier | Modifier::BOLD),
+                ),
+                Text::styled(
+                    format!(
+                        "[{}/{}: scroll][{}: search][{}/{}: next/prev match][{}: back]",
+                        label(Action::Down),
+                        label(Action::Up),
+                        label(Action::Search),
+                        label(Action::NextMatch),
+                        label(Action::PrevMatch),
+                        label(Action::Close)
+                    ),
+                    theme.modal,
+                ),
+            ],
+        };
+        frame.render_widget(Clear, layout[2]);
         frame.render_widget(
-            Paragraph::new(btn_group.iter())
+            Paragraph::new(footer_texts.iter())
                 .block(
                     Block::default()
                         .borders(Borders::ALL - Borders::TOP)
-                        .border_style(BG_STYLE),
+                        .border_style(theme.modal),
                 )
-                .style(BG_STYLE),
+                .style(theme.modal),
             layout[2],
         );
     }
 
-    pub fn propagate(
-        _state: &App,
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        if let AssetsModalState::View(_, text_view) = &state.modal.assets_modal_state {
+            return Self::propagate_text_view(state, text_view, event, tx);
+        }
+
+        // Buttons are clicked as the actions of their keys, and rows are
+        // selected, then opened, by clicking them
+        let action = match &event {
+            Messages::Input(keyevent) => state.config.keymap.action(Scope::Assets, keyevent),
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::Click(MouseTarget::Button(action))) => Some(action),
+                Some(MouseAction::Click(MouseTarget::Asset(index))) => {
+                    match &state.modal.assets_modal_state {
+                        AssetsModalState::Show(list_state, _)
+                            if list_state.selected() == Some(index) =>
+                        {
+                            Some(Action::Open)
+                        }
+                        _ => {
+                            tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
+                                UpdateListSelectedEvent::To(index),
+                            )))
+                            .unwrap();
+                            None
+                        }
+                    }
+                }
+                Some(MouseAction::ScrollDown(MouseTarget::Asset(_))) => Some(Action::Down),
+                Some(MouseAction::ScrollUp(MouseTarget::Asset(_))) => Some(Action::Up),
+                _ => None,
+            },
+            _ => None,
+        };
+        match action {
+            Some(Action::Open) => {
+                tx.send(Messages::AssetsModalAction(AssetsModalActions::OpenFile))
+                    .unwrap();
+            }
+            Some(Action::Close) => {
+                tx.send(Messages::AssetsModalAction(AssetsModalActions::Close))
+                    .unwrap();
+            }
+            Some(Action::TogglePreview) => {
+                tx.send(Messages::AssetsModalAction(
+                    AssetsModalActions::TogglePreview,
+                ))
+                .unwrap();
+            }
+            Some(Action::Down) => {
+                tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
+                    UpdateListSelectedEvent::Next,
+                )))
+                .unwrap();
+            }
+            Some(Action::Up) => {
+                tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
+                    UpdateListSelectedEvent::Prev,
+                )))
+                .unwrap();
+            }
+            Some(Action::Top) => {
+                tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
+                    UpdateListSelectedEvent::Home,
+                )))
+                .unwrap();
+            }
+            Some(Action::Bottom) => {
+                tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
+                    UpdateListSelectedEvent::End,
+                )))
+                .unwrap();
+            }
+            _ => {}
+        };
+        None // Blocks all other inputs
+    }
+
+    fn propagate_text_view(
+        state: &App,
+        text_view: &TextView,
         event: Messages,
         tx: mpsc::Sender<Messages>,
     ) -> Option<Messages> {
-        match event {
-            Messages::Input(keyevent) => match keyevent {
-                key!(Enter) => {
-                    tx.send(Messages::AssetsModalAction(AssetsModalActions::OpenFile))
-                        .unwrap();
-                }
-                key!('c') | key!('C') | key!(Esc) => {
-                    tx.send(Messages::AssetsModalAction(AssetsModalActions::Close))
-                        .unwrap();
-                }
-                key!('j') | key!(Down) => {
-                    tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
-                        UpdateListSelectedEvent::Next,
-                    )))
-                    .unwrap();
+        let keyevent = match event {
+            Messages::Input(keyevent) => keyevent,
+            _ => return None,
+        };
+
+        let keymap = &state.config.keymap;
+        let action = match &text_view.search_input {
+            Some(_) => match (keymap.action(Scope::Input, &keyevent), keyevent) {
+                (Some(Action::Submit), _) => {
+                    Some(TextViewActions::Search(UpdateSearchEvent::Submit))
                 }
-                key!('k') | key!(Up) => {
-                    tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
-                        UpdateListSelectedEvent::Prev,
-                    )))
-                    .unwrap();
+                (Some(Action::Cancel), _) => {
+                    Some(TextViewActions::Search(UpdateSearchEvent::Cancel))
                 }
-                key!('g') => {
-                    tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
-                        UpdateListSelectedEvent::Home,
-                    )))
-                    .unwrap();
+                (Some(Action::DeleteChar), _) => {
+                    Some(TextViewActions::Search(UpdateSearchEvent::Pop))
                 }
-                key!('G') => {
-                    tx.send(Messages::AssetsModalAction(AssetsModalActions::Select(
-                        UpdateListSelectedEvent::End,
-                    )))
-                    .unwrap();
+                (
+                    _,
+                    KeyEvent {
+                        code: KeyCode::Char(c),
+                        modifiers,
+                    },
+                ) if modifiers == KeyModifiers::empty() || modifiers == KeyModifiers::SHIFT => {
+                    Some(TextViewActions::Search(UpdateSearchEvent::Push(c)))
                 }
-                _ => {}
+                _ => None,
+            },
+            None => match keymap.action(Scope::TextView, &keyevent) {
+                Some(Action::Down) => Some(TextViewActions::Scroll(1)),
+                Some(Action::Up) => Some(TextViewActions::Scroll(-1)),
+                Some(Action::PageDown) => Some(TextViewActions::PageDown),
+                Some(Action::PageUp) => Some(TextViewActions::PageUp),
+                Some(Action::Top) => Some(TextViewActions::Home),
+                Some(Action::Bottom) => Some(TextViewActions::End),
+                Some(Action::Search) => Some(TextViewActions::Search(UpdateSearchEvent::Open)),
+                Some(Action::NextMatch) => Some(TextViewActions::NextMatch),
+                Some(Action::PrevMatch) => Some(TextViewActions::PrevMatch),
+                Some(Action::Close) => Some(TextViewActions::Close),
+                _ => None,
             },
-            _ => {}
         };
+        if let Some(action) = action {
+            tx.send(Messages::AssetsModalAction(AssetsModalActions::View(
+                action,
+            )))
+            .unwrap();
+        }
         None // Blocks all other inputs
     }
 }
 
-pub struct HomeWidget<'a> {
-    app: &'a mut App,
+pub struct ErrorModalWidget<'a> {
+    app: &'a App,
 }
 
-impl<'a> HomeWidget<'a> {
-    pub fn new(app: &'a mut App) -> Self {
-        HomeWidget { app }
+impl<'a> ErrorModalWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        ErrorModalWidget { app }
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
-        const UNDERLINE_STYLE: Style = Style {
-            fg: Color::Reset,
-            bg: Color::Reset,
-            modifier: Modifier::UNDERLINED,
-        };
-        const HIGHLIGHT_STYLE: Style = Style {
-            fg: Color::Magenta,
-            bg: Color::Reset,
-            modifier: Modifier::empty(),
-        };
-        const CURRENT_PATH_STYLE: Style = Style {
-            fg: Color::Green,
-            bg: Color::Reset,
-            modifier: Modifier::BOLD,
+        let theme = &self.app.config.theme;
+        let message = match &self.app.modal.error_modal_state {
+            ErrorModalState::Show(message) => message,
+            ErrorModalState::Hidden => unreachable!(),
         };
 
-        let pwd = self.app.home.current_path.to_str().unwrap_or("???");
-        let welcome_messages: [Text; 2] = [
-            Text::raw("Welcome! Choose a file to start:\n\nCurrent Path: "),
-            Text::styled(pwd, CURRENT_PATH_STYLE),
-        ];
-        let footer_messages = [
-            Text::raw("["),
-            Text::styled("q", UNDERLINE_STYLE),
-            Text::raw(": Quit] | ["),
-            Text::styled(
-                "A",
-                match self.app.home.open_mode {
-                    OpenMode::NoAutoSave => Style::default(),
-                    OpenMode::AutoSave => HIGHLIGHT_STYLE,
-                }
-                .modifier(Modifier::UNDERLINED),
-            ),
-            Text::styled(
-                "utoSave",
-                match self.app.home.open_mode {
-                    OpenMode::NoAutoSave => Style::default(),
-                    OpenMode::AutoSave => HIGHLIGHT_STYLE,
-                },
-            ),
-            Text::raw("]"),
-        ];
-        let paths = self.app.home.get_paths().unwrap();
+        let content = ModalRect::new(content);
+        let num_btns = 1;
+        let btn_pad = content.btn_pad(num_btns);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(2),
+                    Constraint::Min(4),
+                    Constraint::Length(2),
+                ]
+                .as_ref(),
+            )
+            .split(content.into());
+
+        // Title
+        let title_texts = [Text::styled(
+            "Error",
+            theme.modal.modifier(theme.modal.modifier | Modifier::BOLD),
+        )];
+        frame.render_widget(Clear, layout[0]);
+        frame.render_widget(
+            Paragraph::new(title_texts.iter())
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL - Borders::BOTTOM)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            layout[0],
+        );
+
+        // Message
+        let message_texts = [Text::raw(message)];
+        frame.render_widget(Clear, layout[1]);
+        frame.render_widget(
+            Paragraph::new(message_texts.iter())
+                .wrap(true)
+                .block(
+                    Block::default()
+                        .borders(Borders::LEFT | Borders::RIGHT)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            layout[1],
+        );
+
+        // The buttons
+        let keymap = &self.app.config.keymap;
+        let btn_group = btn_group(
+            &[("OK", keymap.label(Scope::Error, Action::Close))],
+            btn_pad,
+            theme,
+        );
+        frame.render_widget(Clear, layout[2]);
+        frame.render_widget(
+            Paragraph::new(btn_group.iter())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL - Borders::TOP)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            layout[2],
+        );
+        content
+            .btn_areas(layout[2], num_btns)
+            .into_iter()
+            .for_each(|area| {
+                self.app
+                    .mouse_areas
+                    .add(area, MouseTarget::Button(Action::Close))
+            });
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let action = match &event {
+            Messages::Input(keyevent) => state.config.keymap.action(Scope::Error, keyevent),
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::Click(MouseTarget::Button(action))) => Some(action),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(Action::Close) = action {
+            tx.send(Messages::ErrorModalAction(ErrorModalActions::Close))
+                .unwrap();
+        }
+        None // Blocks all other inputs
+    }
+}
+
+pub struct ConfirmModalWidget<'a> {
+    app: &'a App,
+}
+
+impl<'a> ConfirmModalWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        ConfirmModalWidget { app }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        let message = match &self.app.modal.confirm_modal_state {
+            ConfirmModalState::Show(message, _) => message,
+            ConfirmModalState::Hidden => unreachable!(),
+        };
+
+        let content = ModalRect::new(content);
+        let num_btns = 2;
+        let btn_pad = content.btn_pad(num_btns);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Length(2)].as_ref())
+            .split(content.into());
+
+        // Message
+        let message_texts = [Text::raw(message)];
+        frame.render_widget(Clear, layout[0]);
+        frame.render_widget(
+            Paragraph::new(message_texts.iter())
+                .wrap(true)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL - Borders::BOTTOM)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            layout[0],
+        );
+
+        // The buttons
+        let label = |action| self.app.config.keymap.label(Scope::Confirm, action);
+        let btn_group = btn_group(
+            &[
+                ("OK", label(Action::Okay)),
+                ("CANCEL", label(Action::Cancel)),
+            ],
+            btn_pad,
+            theme,
+        );
+        frame.render_widget(Clear, layout[1]);
+        frame.render_widget(
+            Paragraph::new(btn_group.iter())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL - Borders::TOP)
+                        .border_style(theme.modal),
+                )
+                .style(theme.modal),
+            layout[1],
+        );
+        content
+            .btn_areas(layout[1], num_btns)
+            .into_iter()
+            .zip(&[Action::Okay, Action::Cancel])
+            .for_each(|(area, action)| {
+                self.app.mouse_areas.add(area, MouseTarget::Button(*action))
+            });
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let action = match &event {
+            Messages::Input(keyevent) => state.config.keymap.action(Scope::Confirm, keyevent),
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::Click(MouseTarget::Button(action))) => Some(action),
+                _ => None,
+            },
+            _ => None,
+        };
+        let action = match action {
+            Some(Action::Okay) => ConfirmModalActions::Okay,
+            Some(Action::Cancel) => ConfirmModalActions::Cancel,
+            _ => return None, // Blocks all other inputs
+        };
+        tx.send(Messages::ConfirmModalAction(action)).unwrap();
+        None
+    }
+}
+
+pub struct HomeWidget<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> HomeWidget<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        HomeWidget { app }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+
+        let pwd = self.app.home.current_path.to_str().unwrap_or("???");
+        let welcome_messages: [Text; 2] = [
+            Text::raw("Welcome! Choose a file to start:\n\nCurrent Path: "),
+            Text::styled(pwd, theme.current_path),
+        ];
+        let keymap = &self.app.config.keymap;
+        let autosave_style = match self.app.home.open_mode {
+            OpenMode::NoAutoSave => Style::default(),
+            OpenMode::AutoSave => theme.highlight,
+        };
+        let shuffle_style = match self.app.home.shuffle {
+            false => Style::default(),
+            true => theme.highlight,
+        };
+        let footer_messages = [
+            Text::raw("["),
+            Text::styled(keymap.label(Scope::Home, Action::Quit), theme.hint),
+            Text::raw(": Quit] | ["),
+            Text::styled(
+                keymap.label(Scope::Home, Action::ToggleAutosave),
+                autosave_style.modifier(autosave_style.modifier | Modifier::UNDERLINED),
+            ),
+            Text::styled(": AutoSave", autosave_style),
+            Text::raw("] | ["),
+            Text::styled(
+                keymap.label(Scope::Home, Action::ToggleShuffle),
+                shuffle_style.modifier(shuffle_style.modifier | Modifier::UNDERLINED),
+            ),
+            Text::styled(": Shuffle", shuffle_style),
+            Text::raw("] | ["),
+            Text::styled(keymap.label(Scope::Home, Action::Search), theme.hint),
+            Text::raw(": Find]"),
+        ];
+        let paths = self.app.home.get_paths().unwrap();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
@@ -499,6 +1345,9 @@ impl<'a> HomeWidget<'a> {
             Paragraph::new(footer_messages.iter()).block(Block::default().borders(Borders::TOP)),
             chunks[2],
         );
+        if self.app.home.finder.is_some() {
+            return FinderWidget::new(self.app).draw(frame, chunks[1]);
+        }
         let parent_dir = self.app.home.current_path.parent();
         let list_items = paths.iter().map(|path| {
             if parent_dir.map(|pd| path == pd).unwrap_or(false) {
@@ -518,46 +1367,402 @@ impl<'a> HomeWidget<'a> {
             .highlight_symbol(">")
             .highlight_style(Style::default().modifier(Modifier::REVERSED));
         frame.render_stateful_widget(list_widget, chunks[1], &mut self.app.home.list_state);
+        self.app.mouse_areas.add(chunks[1], MouseTarget::HomeList);
     }
 
     pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        if state.home.finder.is_some() {
+            return FinderWidget::propagate(state, event, tx);
+        }
         match &event {
-            Messages::Input(keyevent) => match keyevent {
-                key!(Enter) => {
+            Messages::Input(keyevent) => match state.config.keymap.action(Scope::Home, keyevent) {
+                Some(Action::Open) => {
                     tx.send(Messages::LoadFile).unwrap();
                     None
                 }
-                key!('j') | key!(Down) => {
+                Some(Action::Search) => {
+                    tx.send(Messages::Finder(FinderActions::Open)).unwrap();
+                    None
+                }
+                Some(Action::Down) => {
                     tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::Next))
                         .unwrap();
                     None
                 }
-                key!('k') | key!(Up) => {
+                Some(Action::Up) => {
                     tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::Prev))
                         .unwrap();
                     None
                 }
-                key!('g') => {
-                    tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::Home))
-                        .unwrap();
+                Some(Action::Top) => {
+                    tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::Home))
+                        .unwrap();
+                    None
+                }
+                Some(Action::Bottom) => {
+                    tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::End))
+                        .unwrap();
+                    None
+                }
+                Some(Action::ToggleAutosave) => {
+                    let current_open_mode = &state.home.open_mode;
+                    tx.send(Messages::SetOpenMode(match current_open_mode {
+                        OpenMode::NoAutoSave => OpenMode::AutoSave,
+                        OpenMode::AutoSave => OpenMode::NoAutoSave,
+                    }))
+                    .unwrap();
+                    None
+                }
+                Some(Action::ToggleShuffle) => {
+                    tx.send(Messages::ToggleShuffle).unwrap();
+                    None
+                }
+                Some(Action::Quit) => {
+                    tx.send(Messages::Quit).unwrap();
+                    None
+                }
+                _ => Some(event),
+            },
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::ScrollDown(MouseTarget::HomeList)) => {
+                    tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::Next))
+                        .unwrap();
+                    None
+                }
+                Some(MouseAction::ScrollUp(MouseTarget::HomeList)) => {
+                    tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::Prev))
+                        .unwrap();
+                    None
+                }
+                _ => Some(event),
+            },
+            _ => Some(event),
+        }
+    }
+}
+
+pub struct FinderWidget<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> FinderWidget<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        FinderWidget { app }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        let finder = self.app.home.finder.as_mut().unwrap();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(3),
+                    Constraint::Min(3),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .split(content);
+
+        // Query
+        let input_texts = [Text::styled(format!("{}_", finder.query), theme.input)];
+        frame.render_widget(
+            Paragraph::new(input_texts.iter())
+                .block(Block::default().borders(Borders::ALL).title("Find")),
+            chunks[0],
+        );
+
+        // Matches, with the progress and title of exams
+        let (title, list_items) = match &finder.entries {
+            Some(entries) => {
+                let list_items: Vec<Text> = finder
+                    .matches
+                    .iter()
+                    .filter_map(|pos| entries.get(*pos))
+                    .map(|entry| match &entry.meta {
+                        Some(meta) => Text::raw(format!(
+                            "{} [{}/{}] {}",
+                            entry.name,
+                            meta.num_answered,
+                            meta.num_questions,
+                            meta.title.as_deref().unwrap_or("")
+                        )),
+                        None => Text::raw(format!("{} [?]", entry.name)),
+                    })
+                    .collect();
+                let title = format!("Exams ({}/{})", finder.matches.len(), entries.len());
+                (title, list_items)
+            }
+            None => ("Indexing...".to_owned(), vec![]),
+        };
+        let list_widget = List::new(list_items.into_iter())
+            .block(Block::default().borders(Borders::ALL).title(&title))
+            .highlight_symbol(">")
+            .highlight_style(Style::default().modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list_widget, chunks[1], &mut finder.list_state);
+        self.app.mouse_areas.add(chunks[1], MouseTarget::HomeList);
+
+        // Usage
+        let keymap = &self.app.config.keymap;
+        let usage_texts = [Text::styled(
+            format!(
+                "[{}: open][{}/{}: select][{}: close]",
+                keymap.label(Scope::Input, Action::Submit),
+                keymap.label(Scope::Finder, Action::Down),
+                keymap.label(Scope::Finder, Action::Up),
+                keymap.label(Scope::Input, Action::Cancel)
+            ),
+            theme.hint,
+        )];
+        frame.render_widget(Paragraph::new(usage_texts.iter()), chunks[2]);
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let keymap = &state.config.keymap;
+        let action = match &event {
+            Messages::Input(keyevent) => {
+                match (
+                    keymap.action_in(&[Scope::Input, Scope::Finder], keyevent),
+                    keyevent,
+                ) {
+                    (Some(Action::Submit), _) => Some(FinderActions::OpenFile),
+                    (Some(Action::Cancel), _) => Some(FinderActions::Close),
+                    (Some(Action::DeleteChar), _) => Some(FinderActions::Pop),
+                    (Some(Action::Down), _) => {
+                        Some(FinderActions::Select(UpdateListSelectedEvent::Next))
+                    }
+                    (Some(Action::Up), _) => {
+                        Some(FinderActions::Select(UpdateListSelectedEvent::Prev))
+                    }
+                    (
+                        _,
+                        KeyEvent {
+                            code: KeyCode::Char(c),
+                            modifiers,
+                        },
+                    ) if *modifiers == KeyModifiers::empty()
+                        || *modifiers == KeyModifiers::SHIFT =>
+                    {
+                        Some(FinderActions::Push(*c))
+                    }
+                    _ => None,
+                }
+            }
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::ScrollDown(MouseTarget::HomeList)) => {
+                    Some(FinderActions::Select(UpdateListSelectedEvent::Next))
+                }
+                Some(MouseAction::ScrollUp(MouseTarget::HomeList)) => {
+                    Some(FinderActions::Select(UpdateListSelectedEvent::Prev))
+                }
+                _ => None,
+            },
+            _ => return Some(event),
+        };
+        if let Some(action) = action {
+            tx.send(Messages::Finder(action)).unwrap();
+        }
+        None // Blocks all other inputs
+    }
+}
+
+pub struct ResultsWidget<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> ResultsWidget<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        ResultsWidget { app }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+
+        let exam = self.app.exam.as_ref().unwrap();
+        let report = Report::new(exam);
+        let filename = self
+            .app
+            .home
+            .exam_src
+            .as_ref()
+            .and_then(|filepath| filepath.file_stem())
+            .and_then(|filename| filename.to_str())
+            .unwrap_or("???");
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            // Title bar, summary, details and footer
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Length(5),
+                    Constraint::Min(6),
+                    Constraint::Length(2),
+                ]
+                .as_ref(),
+            )
+            .split(content);
+
+        // Title bar
+        frame.render_widget(
+            Paragraph::new(
+                [Text::styled(
+                    format!("Results: {}", filename),
+                    Style::default().modifier(Modifier::BOLD),
+                )]
+                .iter(),
+            )
+            .style(Style::default().modifier(Modifier::REVERSED))
+            .alignment(Alignment::Center),
+            main_chunks[0],
+        );
+
+        // Summary
+        let tally = &report.tally;
+        let summary_texts = [
+            Text::raw("Correct: "),
+            Text::styled(format!("{}", tally.correct), theme.correct),
+            Text::raw("  Partial: "),
+            Text::styled(format!("{}", tally.partial), theme.partial),
+            Text::raw("  Wrong: "),
+            Text::styled(format!("{}", tally.wrong), theme.wrong),
+            Text::raw("  Pending: "),
+            Text::styled(format!("{}", tally.pending), theme.pending),
+            Text::raw(format!(
+                "\nScore: {}/{} ({:.1}%)",
+                format_points(tally.points),
+                format_points(tally.max_points),
+                tally.percentage()
+            )),
+        ];
+        frame.render_widget(
+            Paragraph::new(summary_texts.iter())
+                .block(Block::default().borders(Borders::ALL).title("Summary"))
+                .wrap(true),
+            main_chunks[1],
+        );
+
+        // Wrong and partial answers, and tags
+        let detail_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+            .split(main_chunks[2]);
+
+        let missed_items = report.missed_items.iter().map(|index| {
+            let text = exam
+                .question_at(*index)
+                .map(|item| missed_summary(item, exam.scoring))
+                .unwrap_or_default();
+            Text::raw(format!("{:3}. {}", exam.display_number(*index), text))
+        });
+        frame.render_stateful_widget(
+            List::new(missed_items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Wrong and partial answers"),
+                )
+                .highlight_symbol(">")
+                .highlight_style(Style::default().modifier(Modifier::REVERSED)),
+            detail_chunks[0],
+            &mut self.app.results.list_state,
+        );
+
+        let tag_texts: Vec<Text> = report
+            .tags
+            .iter()
+            .map(|(tag, tally)| {
+                Text::raw(format!(
+                    "{}: {}/{} ({:.1}%)\n",
+                    tag,
+                    format_points(tally.points),
+                    format_points(tally.max_points),
+                    tally.percentage()
+                ))
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(tag_texts.iter())
+                .block(Block::default().borders(Borders::ALL).title("By tag"))
+                .wrap(true),
+            detail_chunks[1],
+        );
+
+        // Footer
+        let footer_text = match &self.app.results.status {
+            Some(status) => status.to_owned(),
+            None => {
+                let keymap = &self.app.config.keymap;
+                let label = |action| keymap.label(Scope::Results, action);
+                format!(
+                    "[{}: back][{},{}: select][{}: go to question][{}: export]",
+                    label(Action::Back),
+                    label(Action::Down),
+                    label(Action::Up),
+                    label(Action::Open),
+                    label(Action::Export)
+                )
+            }
+        };
+        frame.render_widget(
+            Paragraph::new([Text::raw(footer_text)].iter())
+                .block(Block::default().borders(Borders::TOP))
+                .alignment(Alignment::Center),
+            main_chunks[3],
+        );
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        match &event {
+            Messages::Input(keyevent) => match state.config.keymap.action(Scope::Results, keyevent)
+            {
+                Some(Action::Back) => {
+                    tx.send(Messages::ChangeRoute(AppRoute::DoExam)).unwrap();
+                    None
+                }
+                Some(Action::Open) => {
+                    let exam = state.exam.as_ref()?;
+                    let selected = state.results.list_state.selected()?;
+                    let index = *Report::new(exam).missed_items.get(selected)?;
+                    tx.send(Messages::ChangeRoute(AppRoute::DoExam)).unwrap();
+                    tx.send(Messages::UpdateQuestionIndex(
+                        UpdateQuestionIndexEvent::Set(index),
+                    ))
+                    .unwrap();
+                    None
+                }
+                Some(Action::Down) => {
+                    tx.send(Messages::UpdateResultsSelected(
+                        UpdateListSelectedEvent::Next,
+                    ))
+                    .unwrap();
+                    None
+                }
+                Some(Action::Up) => {
+                    tx.send(Messages::UpdateResultsSelected(
+                        UpdateListSelectedEvent::Prev,
+                    ))
+                    .unwrap();
                     None
                 }
-                key!('G') => {
-                    tx.send(Messages::UpdateHomeSelected(UpdateListSelectedEvent::End))
-                        .unwrap();
+                Some(Action::Top) => {
+                    tx.send(Messages::UpdateResultsSelected(
+                        UpdateListSelectedEvent::Home,
+                    ))
+                    .unwrap();
                     None
                 }
-                key!('a') => {
-                    let current_open_mode = &state.home.open_mode;
-                    tx.send(Messages::SetOpenMode(match current_open_mode {
-                        OpenMode::NoAutoSave => OpenMode::AutoSave,
-                        OpenMode::AutoSave => OpenMode::NoAutoSave,
-                    }))
+                Some(Action::Bottom) => {
+                    tx.send(Messages::UpdateResultsSelected(
+                        UpdateListSelectedEvent::End,
+                    ))
                     .unwrap();
                     None
                 }
-                key!('q') => {
-                    tx.send(Messages::Quit).unwrap();
+                Some(Action::Export) => {
+                    tx.send(Messages::ExportReport).unwrap();
                     None
                 }
                 _ => Some(event),
@@ -577,8 +1782,13 @@ impl<'a> ExamWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
-        let sidebar_length = self.app.config.items_per_line * 4 + 1;
         let exam = self.app.exam.as_ref().unwrap();
+        let searching = exam.display.search_input.is_some() || exam.display.search.is_some();
+        let sidebar_length = match searching {
+            // Leaves room for the results of the search
+            true => (self.app.config.items_per_line * 4 + 1).max(36),
+            false => self.app.config.items_per_line * 4 + 1,
+        };
 
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -595,8 +1805,14 @@ impl<'a> ExamWidget<'a> {
             .split(content);
 
         // Title bar
-        let filepath = self.app.home.get_selected_path().unwrap();
-        let filename = filepath.file_stem().unwrap().to_str().unwrap();
+        let filename = self
+            .app
+            .home
+            .exam_src
+            .as_ref()
+            .and_then(|filepath| filepath.file_stem())
+            .and_then(|filename| filename.to_str())
+            .unwrap_or("???");
 
         let title = match exam.unsaved_changes {
             true => format!("{}[+]", &filename),
@@ -606,6 +1822,22 @@ impl<'a> ExamWidget<'a> {
             OpenMode::NoAutoSave => format!("{}", &title),
             OpenMode::AutoSave => format!("{} [autosave]", &title),
         };
+        let title = match (&exam.display.review_items, exam.display.studying) {
+            (Some(review_items), true) => format!("{} [study: {}]", &title, review_items.len()),
+            (Some(review_items), false) => format!("{} [review: {}]", &title, review_items.len()),
+            (None, _) => title,
+        };
+        let title = match exam.time_left() {
+            None => title,
+            Some(0) => format!("{} [time up]", &title),
+            Some(time_left) => format!(
+                "{} [{:02}:{:02}:{:02} left]",
+                &title,
+                time_left / 3600,
+                time_left / 60 % 60,
+                time_left % 60
+            ),
+        };
 
         frame.render_widget(
             Paragraph::new(
@@ -626,25 +1858,41 @@ impl<'a> ExamWidget<'a> {
             let num_answered = exam
                 .questions
                 .iter()
-                .filter(|item| match item {
-                    Item::Question(question) => question.user_selection.is_empty(),
-                    _ => false,
-                })
-                .collect::<Vec<&Item>>()
-                .len();
+                .filter(|item| matches!(item.get_result(exam.scoring), QuestionResult::Pending))
+                .count();
             Gauge::default()
                 .ratio(1f64 - num_answered as f64 / num_questions as f64)
-                .style(
-                    Style::default()
-                        .fg(Color::Rgb(147, 161, 161))
-                        .bg(Color::Rgb(238, 232, 213)),
-                )
+                .style(self.app.config.theme.progress)
         };
         frame.render_widget(progress_bar, main_chunks[2]);
 
         let main_chunks = match &self.app.config.show_usage {
             // Has usage footer
             true => {
+                let keymap = &self.app.config.keymap;
+                let label = |action| keymap.label(Scope::Exam, action);
+                let item = exam.question_at(exam.display.question_index);
+                let scopes = item
+                    .map(item_scopes)
+                    .unwrap_or(&[Scope::Global, Scope::Exam]);
+                // Selections are toggled by their letters
+                let toggle_hint = match item {
+                    Some(Item::Question(question)) => format!(
+                        "[{}: toggle answer]",
+                        label_keys_hint(keymap, scopes, question.num_selections())
+                    ),
+                    _ => String::new(),
+                };
+                // Digits bound to actions are not typed into the jump box
+                let bound_digits: Vec<String> = keymap
+                    .bound_chars(scopes, '0'..='9')
+                    .iter()
+                    .map(char::to_string)
+                    .collect();
+                let goto_hint = match bound_digits.is_empty() {
+                    true => "0-9".to_owned(),
+                    false => format!("0-9 but {}", bound_digits.join("/")),
+                };
                 let main_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     // Main View and Sidebar
@@ -653,11 +1901,29 @@ impl<'a> ExamWidget<'a> {
 
                 frame.render_widget(
                     Paragraph::new(
-                        [Text::raw(
-                            "Usage: [q: quit][a-h: toggle answer]\
-                                    [space: toggle view][0-9: goto]\
-                                    [n,p: change page][^s: save]",
-                        )]
+                        [Text::raw(format!(
+                            "Usage: [{}: quit]{}\
+                             [{}: toggle view][{}: goto]\
+                             [{},{}: change page][{}: flag][{},{}: flagged]\
+                             [{}: search][{}: save][{}: results][{}: review wrong]\
+                             [{}: review flagged][{}: reset review][{}: study due]",
+                            label(Action::Quit),
+                            toggle_hint,
+                            label(Action::ToggleAnswer),
+                            goto_hint,
+                            label(Action::NextItem),
+                            label(Action::PrevItem),
+                            label(Action::ToggleFlag),
+                            label(Action::PrevFlagged),
+                            label(Action::NextFlagged),
+                            label(Action::Search),
+                            label(Action::Save),
+                            label(Action::ShowResults),
+                            label(Action::ToggleReview),
+                            label(Action::ToggleFlaggedReview),
+                            label(Action::ResetReview),
+                            label(Action::ToggleStudy),
+                        ))]
                         .iter(),
                     )
                     .alignment(Alignment::Center),
@@ -678,12 +1944,15 @@ impl<'a> ExamWidget<'a> {
 
         ItemWidget::new(self.app).draw(frame, main_chunks[0]);
 
-        match exam.jumpbox_value {
+        match (searching, exam.jumpbox_value) {
+            (true, _) => {
+                ExamSearchWidget::new(self.app).draw(frame, main_chunks[1]);
+            }
             // Do not display jumpbox if its value is zero.
-            0 => {
+            (false, 0) => {
                 ExamItemsWidget::new(self.app).draw(frame, main_chunks[1]);
             }
-            _ => {
+            (false, _) => {
                 let sidebar_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     // Jumpbar and ExamItems
@@ -697,8 +1966,22 @@ impl<'a> ExamWidget<'a> {
     }
 
     pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
-        match &event {
-            Messages::Input(key!('q')) => {
+        let display = &state.exam.as_ref()?.display;
+        // Keys are typed into the answer while editing
+        if display.answer_input.is_some() {
+            return TextAnswerWidget::propagate(state, event, tx);
+        }
+        // The search takes the keys while typed or listing its results
+        let event = match display.search_input.is_some() || display.search.is_some() {
+            true => ExamSearchWidget::propagate(state, event, tx.clone())?,
+            false => event,
+        };
+        let action = match &event {
+            Messages::Input(keyevent) => state.config.keymap.action(Scope::Exam, keyevent),
+            _ => None,
+        };
+        match action {
+            Some(Action::Quit) => {
                 let exam = state.exam.as_ref().unwrap();
                 match exam.unsaved_changes {
                     true => tx
@@ -710,13 +1993,40 @@ impl<'a> ExamWidget<'a> {
                 }
                 return None;
             }
-            Messages::Input(key!(^'s')) => {
+            Some(Action::Save) => {
                 tx.send(Messages::SaveModalAction(SaveModalActions::Open(
                     SaveModalState::ShowSave,
                 )))
                 .unwrap();
                 return None;
             }
+            Some(Action::ShowResults) => {
+                tx.send(Messages::ChangeRoute(AppRoute::Results)).unwrap();
+                return None;
+            }
+            Some(Action::ToggleReview) => {
+                tx.send(Messages::ToggleReview).unwrap();
+                return None;
+            }
+            Some(Action::ToggleFlaggedReview) => {
+                tx.send(Messages::ToggleFlaggedReview).unwrap();
+                return None;
+            }
+            Some(Action::ResetReview) => {
+                tx.send(Messages::ResetReviewItems).unwrap();
+                return None;
+            }
+            Some(Action::ToggleStudy) => {
+                tx.send(Messages::ToggleStudy).unwrap();
+                return None;
+            }
+            Some(Action::Search) => {
+                tx.send(Messages::ExamSearch(ExamSearchActions::Search(
+                    UpdateSearchEvent::Open,
+                )))
+                .unwrap();
+                return None;
+            }
             _ => {}
         };
         JumpBarWidget::propagate(state, event, tx.clone())
@@ -744,25 +2054,58 @@ impl<'a> ItemWidget<'a> {
             Item::Question(question) => {
                 QuestionWidget::new(self.app, &question, &exam.display).draw(frame, content)
             }
-            _ => {}
+            Item::Card(card) => CardWidget::new(self.app, card, &exam.display).draw(frame, content),
+            Item::Text(_) | Item::Numeric(_) => {
+                TextAnswerWidget::new(self.app, item, &exam.display).draw(frame, content)
+            }
+            Item::TrueFalse(question) => {
+                TrueFalseWidget::new(self.app, question, &exam.display).draw(frame, content)
+            }
+            Item::Ordering(question) => {
+                OrderingWidget::new(self.app, question, &exam.display).draw(frame, content)
+            }
+            Item::Matching(question) => {
+                MatchingWidget::new(self.app, question, &exam.display).draw(frame, content)
+            }
         }
     }
 
     pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
         let exam = state.exam.as_ref().unwrap();
         match event {
-            Messages::Input(key!('t')) => {
+            Messages::Input(keyevent)
+                if state.config.keymap.action(Scope::Exam, &keyevent)
+                    == Some(Action::OpenAssets) =>
+            {
                 tx.send(Messages::AssetsModalAction(AssetsModalActions::Open))
                     .unwrap();
                 None
             }
+            // The wheel scrolls the question of every kind of item
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(&mouseevent) {
+                Some(MouseAction::ScrollDown(MouseTarget::Question)) => {
+                    scroll_question(exam, 1, &tx);
+                    None
+                }
+                Some(MouseAction::ScrollUp(MouseTarget::Question)) => {
+                    scroll_question(exam, -1, &tx);
+                    None
+                }
+                _ => Some(event),
+            },
             _ => Some(event),
         }
         .and_then(
             |event| match exam.question_at(exam.display.question_index) {
                 Some(item) => match item {
                     Item::Question(_) => QuestionWidget::propagate(state, event, tx),
-                    _ => Some(event),
+                    Item::Card(_) => CardWidget::propagate(state, event, tx),
+                    Item::Text(_) | Item::Numeric(_) => {
+                        TextAnswerWidget::propagate(state, event, tx)
+                    }
+                    Item::TrueFalse(_) => TrueFalseWidget::propagate(state, event, tx),
+                    Item::Ordering(_) => OrderingWidget::propagate(state, event, tx),
+                    Item::Matching(_) => MatchingWidget::propagate(state, event, tx),
                 },
                 _ => Some(event),
             },
@@ -786,17 +2129,11 @@ impl<'a> QuestionWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
-        const ASSETS_STYLE: Style = Style {
-            fg: Color::Red,
-            bg: Color::Reset,
-            modifier: Modifier::empty(),
-        };
+        let theme = &self.app.config.theme;
         let exam = &self.app.exam.as_ref().unwrap();
-        let question_title = format!(
-            "Question ({}/{})",
-            &self.display.question_index + 1,
-            exam.num_questions()
-        );
+        let question_title = item_title("Question", exam, self.display);
+        // Selections may be shuffled
+        let selection_order = exam.selection_order(self.display.question_index);
         const WRAPPER_SELECT: [&str; 2] = ["(", ")"];
         const WRAPPER_MULTSEL: [&str; 2] = ["[", "]"];
         let current_wrapper = if self.question.num_should_selects() == 1usize {
@@ -805,23 +2142,26 @@ impl<'a> QuestionWidget<'a> {
             WRAPPER_MULTSEL
         };
 
-        // Adds indicator for assets
-        let mut question_display = vec![Text::raw(&self.question.question)];
         let current_item = exam.question_at(exam.display.question_index).unwrap();
         let num_assets = current_item.get_assets().len();
-        if num_assets > 0 {
-            question_display.push(Text::styled("\n\n[Asse", ASSETS_STYLE));
-            question_display.push(Text::styled(
-                "t",
-                ASSETS_STYLE.modifier(Modifier::UNDERLINED),
-            ));
-            question_display.push(Text::styled(format!("s: {}]", num_assets), ASSETS_STYLE));
-        }
+        let question_display = question_texts(
+            &self.question.question,
+            num_assets,
+            content.width,
+            theme,
+            &self.app.config.keymap,
+            self.display.search_query(),
+        );
 
         // Question + Selections
+        let num_selections = self
+            .question
+            .num_selections()
+            .min(SelectionFlags::MAX_SELECTIONS) as u16;
+        let selections_height = (num_selections + 4).max(12);
         let two_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(10), Constraint::Length(12)].as_ref())
+            .constraints([Constraint::Min(10), Constraint::Length(selections_height)].as_ref())
             .split(content);
 
         let three_chunks = Layout::default()
@@ -829,7 +2169,7 @@ impl<'a> QuestionWidget<'a> {
             .constraints(
                 [
                     Constraint::Min(10),
-                    Constraint::Length(12),
+                    Constraint::Length(selections_height),
                     Constraint::Min(10),
                 ]
                 .as_ref(),
@@ -846,53 +2186,47 @@ impl<'a> QuestionWidget<'a> {
                                 .borders(Borders::ALL)
                                 .title(&question_title),
                         )
-                        .wrap(true)
                         .scroll(self.display.question_scroll_pos),
                     two_chunks[0],
                 );
+                self.app
+                    .mouse_areas
+                    .add(two_chunks[0], MouseTarget::Question);
 
                 // Selections
                 let selections_display = {
-                    let selections_state = self
-                        .question
-                        .selections
+                    let selections_state = selection_order
                         .iter()
-                        .enumerate()
-                        .take(8)
-                        .map(|(index, sel)| {
-                            let selection_flag: u8 = 0b1 << index;
-                            ToggleButtonState {
-                                text: sel.text.clone(),
-                                selected: self.question.user_selection.is_selected(selection_flag),
-                            }
+                        .filter_map(|index| Some((*index, self.question.selections.get(*index)?)))
+                        .map(|(index, sel)| ToggleButtonState {
+                            text: sel.text.clone(),
+                            selected: self.question.user_selection.is_selected(index),
                         })
                         .collect();
-                    ToggleButtons::new(selections_state).wrapper(current_wrapper)
+                    ToggleButtons::new(selections_state)
+                        .wrapper(current_wrapper)
+                        .syntax(theme.syntax)
+                        .highlight(self.display.search_query(), theme.search_match)
                 };
+                self.add_selection_areas(&selections_display, two_chunks[1]);
                 frame.render_widget(selections_display, two_chunks[1]);
             }
             true => {
                 // Question
-                let question_text = [Text::raw(&self.question.question)];
-                let question_block = Paragraph::new(question_text.iter())
+                let question_block = Paragraph::new(question_display.iter())
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
                             .title(&question_title),
                     )
-                    .scroll(self.display.question_scroll_pos)
-                    .wrap(true);
+                    .scroll(self.display.question_scroll_pos);
 
                 // Selection
-                let selections_state = self
-                    .question
-                    .selections
+                let selections_state = selection_order
                     .iter()
-                    .enumerate()
-                    .take(8)
+                    .filter_map(|index| Some((*index, self.question.selections.get(*index)?)))
                     .map(|(index, sel)| {
-                        let selection_flag: u8 = 0b1 << index;
-                        let selected = self.question.user_selection.is_selected(selection_flag);
+                        let selected = self.question.user_selection.is_selected(index);
 
                         ToggleButtonState {
                             text: format!(
@@ -910,103 +2244,628 @@ impl<'a> QuestionWidget<'a> {
                         }
                     })
                     .collect();
-                let selections_block =
-                    ToggleButtons::new(selections_state).wrapper(current_wrapper);
+                let selections_block = ToggleButtons::new(selections_state)
+                    .wrapper(current_wrapper)
+                    .syntax(theme.syntax)
+                    .highlight(self.display.search_query(), theme.search_match);
 
                 // Answer
                 let answer = match self.question.answer.as_ref() {
                     Some(answer) => answer,
                     None => "",
                 };
-                let answer_text = [Text::raw(answer)];
+                let answer_text = markdown::highlight(
+                    markdown::render(answer, content.width, &theme.syntax),
+                    self.display.search_query(),
+                    theme.search_match,
+                );
                 let answer_block = Paragraph::new(answer_text.iter())
                     .block(Block::default().borders(Borders::TOP).title("­Answer"))
-                    .scroll(self.display.question_scroll_pos)
-                    .wrap(true);
+                    .scroll(self.display.question_scroll_pos);
+
+                match self.question.answer.is_some() {
+                    true => {
+                        // Question + Selection
+                        frame.render_widget(question_block, three_chunks[0]);
+                        self.add_selection_areas(&selections_block, three_chunks[1]);
+                        frame.render_widget(selections_block, three_chunks[1]);
+                        frame.render_widget(answer_block, three_chunks[2]);
+                        let mouse_areas = &self.app.mouse_areas;
+                        mouse_areas.add(three_chunks[0], MouseTarget::Question);
+                        mouse_areas.add(three_chunks[2], MouseTarget::Question);
+                    }
+                    false => {
+                        // Question + Selection + Answer
+                        frame.render_widget(question_block, two_chunks[0]);
+                        self.add_selection_areas(&selections_block, two_chunks[1]);
+                        frame.render_widget(selections_block, two_chunks[1]);
+                        self.app
+                            .mouse_areas
+                            .add(two_chunks[0], MouseTarget::Question);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Selection rows are clicked by their position on screen.
+    fn add_selection_areas(&self, selections: &ToggleButtons, area: Rect) {
+        selections
+            .button_areas(area)
+            .into_iter()
+            .enumerate()
+            .for_each(|(pos, area)| self.app.mouse_areas.add(area, MouseTarget::Selection(pos)));
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        match event {
+            Messages::Input(keyevent) => match (
+                state
+                    .config
+                    .keymap
+                    .action_in(&[Scope::Exam, Scope::Item], &keyevent),
+                keyevent,
+            ) {
+                (Some(Action::Down), _) => {
+                    scroll_question(state.exam.as_ref()?, 1, &tx);
+                    None
+                }
+                (Some(Action::Up), _) => {
+                    scroll_question(state.exam.as_ref()?, -1, &tx);
+                    None
+                }
+                (Some(Action::ToggleAnswer), _) => {
+                    tx.send(Messages::ToggleExamResult).unwrap();
+                    return None;
+                }
+                (Some(Action::ToggleFlag), _) => {
+                    tx.send(Messages::ToggleFlag).unwrap();
+                    None
+                }
+                // Selections are toggled by their labels. Labels whose keys are
+                // bound to other actions (e.g. `j`, `n`) can still be toggled
+                // with the upper case letter, or with Alt (e.g. Alt-q).
+                (
+                    None,
+                    KeyEvent {
+                        code: KeyCode::Char(c),
+                        modifiers,
+                    },
+                ) if modifiers == KeyModifiers::empty() || modifiers == KeyModifiers::ALT => {
+                    match SelectionFlags::index_of_label(c)
+                        .and_then(|pos| Self::selection_at(state, pos))
+                    {
+                        Some(sel) => {
+                            tx.send(Messages::ToggleSelection(sel)).unwrap();
+                            None
+                        }
+                        None => Some(event),
+                    }
+                }
+                _ => Some(event),
+            },
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(&mouseevent) {
+                Some(MouseAction::Click(MouseTarget::Selection(pos))) => {
+                    match Self::selection_at(state, pos) {
+                        Some(sel) => {
+                            tx.send(Messages::ToggleSelection(sel)).unwrap();
+                            None
+                        }
+                        None => Some(event),
+                    }
+                }
+                _ => Some(event),
+            },
+            _ => Some(event),
+        }
+    }
+
+    /// Maps the displayed position of a selection back to the selection
+    fn selection_at(state: &App, pos: usize) -> Option<SelectionFlags> {
+        let exam = state.exam.as_ref()?;
+        let selection_order = exam.selection_order(exam.display.question_index);
+        selection_order
+            .get(pos)
+            .and_then(|index| SelectionFlags::from_index(*index))
+    }
+}
+
+pub struct CardWidget<'a> {
+    app: &'a App,
+    card: &'a Card,
+    display: &'a DoExamDisplay,
+}
+
+impl<'a> CardWidget<'a> {
+    pub fn new(app: &'a App, card: &'a Card, display: &'a DoExamDisplay) -> Self {
+        CardWidget { app, card, display }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        const KIND: &str = "Card";
+        let exam = &self.app.exam.as_ref().unwrap();
+        let item_title = item_title(KIND, exam, self.display);
+        let show_answer = self.display.card_flipped || self.display.display_answer;
+
+        let num_assets = self.card.assets.len();
+        let question_display = question_texts(
+            &self.card.question,
+            num_assets,
+            content.width,
+            theme,
+            &self.app.config.keymap,
+            self.display.search_query(),
+        );
+
+        // Front + Grade, or Front + Back + Grade
+        let chunks = match show_answer {
+            false => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(10), Constraint::Length(2)].as_ref())
+                .split(content),
+            true => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(10),
+                        Constraint::Min(10),
+                        Constraint::Length(2),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+        };
+
+        // Front
+        frame.render_widget(
+            Paragraph::new(question_display.iter())
+                .block(Block::default().borders(Borders::ALL).title(&item_title))
+                .scroll(self.display.question_scroll_pos),
+            chunks[0],
+        );
+        self.app.mouse_areas.add(chunks[0], MouseTarget::Question);
+
+        // Back
+        if show_answer {
+            let answer_text = markdown::highlight(
+                markdown::render(&self.card.answer, content.width, &theme.syntax),
+                self.display.search_query(),
+                theme.search_match,
+            );
+            frame.render_widget(
+                Paragraph::new(answer_text.iter())
+                    .block(Block::default().borders(Borders::TOP).title("Answer"))
+                    .scroll(self.display.question_scroll_pos),
+                chunks[1],
+            );
+            self.app.mouse_areas.add(chunks[1], MouseTarget::Question);
+        }
+
+        // Grade
+        let grade_text = match self.card.user_grade {
+            CardGrade::Pending => "Not graded",
+            CardGrade::Known => "Knew it",
+            CardGrade::Unknown => "Didn't know",
+        };
+        let keymap = &self.app.config.keymap;
+        let grade_display = match show_answer {
+            false => vec![
+                Text::raw(format!("{} | [", grade_text)),
+                Text::styled(keymap.label(Scope::Exam, Action::ToggleAnswer), theme.hint),
+                Text::raw(": flip]"),
+            ],
+            true => vec![
+                Text::raw(format!("{} | [", grade_text)),
+                Text::styled(keymap.label(Scope::Card, Action::Known), theme.hint),
+                Text::raw(": knew it] ["),
+                Text::styled(keymap.label(Scope::Card, Action::Unknown), theme.hint),
+                Text::raw(": didn't]"),
+            ],
+        };
+        frame.render_widget(
+            Paragraph::new(grade_display.iter())
+                .block(Block::default().borders(Borders::TOP))
+                .alignment(Alignment::Center),
+            chunks[chunks.len() - 1],
+        );
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let exam = state.exam.as_ref().unwrap();
+        let show_answer = exam.display.card_flipped || exam.display.display_answer;
+        match event {
+            Messages::Input(keyevent) => match state
+                .config
+                .keymap
+                .action_in(&[Scope::Exam, Scope::Item, Scope::Card], &keyevent)
+            {
+                Some(Action::ToggleAnswer) => {
+                    tx.send(Messages::FlipCard).unwrap();
+                    None
+                }
+                Some(Action::ToggleFlag) => {
+                    tx.send(Messages::ToggleFlag).unwrap();
+                    None
+                }
+                // Self-grading is only available once the back is revealed
+                Some(Action::Known) if show_answer => {
+                    tx.send(Messages::GradeCard(CardGrade::Known)).unwrap();
+                    None
+                }
+                Some(Action::Unknown) if show_answer => {
+                    tx.send(Messages::GradeCard(CardGrade::Unknown)).unwrap();
+                    None
+                }
+                Some(Action::Down) => {
+                    scroll_question(exam, 1, &tx);
+                    None
+                }
+                Some(Action::Up) => {
+                    scroll_question(exam, -1, &tx);
+                    None
+                }
+                _ => Some(event),
+            },
+            _ => Some(event),
+        }
+    }
+}
+
+pub struct TextAnswerWidget<'a> {
+    app: &'a App,
+    item: &'a Item,
+    display: &'a DoExamDisplay,
+}
+
+impl<'a> TextAnswerWidget<'a> {
+    pub fn new(app: &'a App, item: &'a Item, display: &'a DoExamDisplay) -> Self {
+        TextAnswerWidget { app, item, display }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        let exam = &self.app.exam.as_ref().unwrap();
+
+        // Expected answer and explanation
+        let (kind, expected, explanation) = match self.item {
+            Item::Text(question) => (
+                "Fill in the blank",
+                question.accepted_answers.join(" | "),
+                &question.answer,
+            ),
+            Item::Numeric(question) => (
+                "Numeric",
+                match question.tolerance == 0f64 {
+                    true => format!("{}", question.value),
+                    false => format!("{} ± {}", question.value, question.tolerance.abs()),
+                },
+                &question.answer,
+            ),
+            _ => return,
+        };
+        let user_answer = self.item.get_user_answer().unwrap_or("");
+
+        let item_title = item_title(kind, exam, self.display);
+
+        let num_assets = self.item.get_assets().len();
+        let question_display = question_texts(
+            self.item.get_text(),
+            num_assets,
+            content.width,
+            theme,
+            &self.app.config.keymap,
+            self.display.search_query(),
+        );
+
+        // Question + Input, or Question + Input + Answer
+        let chunks = match self.display.display_answer {
+            false => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(10), Constraint::Length(3)].as_ref())
+                .split(content),
+            true => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(10),
+                        Constraint::Length(3),
+                        Constraint::Min(5),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+        };
+
+        // Question
+        frame.render_widget(
+            Paragraph::new(question_display.iter())
+                .block(Block::default().borders(Borders::ALL).title(&item_title))
+                .scroll(self.display.question_scroll_pos),
+            chunks[0],
+        );
+        self.app.mouse_areas.add(chunks[0], MouseTarget::Question);
+
+        // Input
+        let keymap = &self.app.config.keymap;
+        let input_display = match (&self.display.answer_input, self.display.display_answer) {
+            (Some(input), _) => vec![
+                Text::styled(format!("{}_", input), theme.input),
+                Text::raw(" ["),
+                Text::styled(keymap.label(Scope::Input, Action::Submit), theme.hint),
+                Text::raw(": submit] ["),
+                Text::styled(keymap.label(Scope::Input, Action::Cancel), theme.hint),
+                Text::raw(": cancel]"),
+            ],
+            (None, true) => vec![Text::styled(
+                user_answer,
+                match self.item.get_result(exam.scoring) {
+                    QuestionResult::Correct => theme.correct,
+                    _ => theme.wrong,
+                },
+            )],
+            (None, false) => vec![
+                Text::raw(match user_answer.is_empty() {
+                    true => "Not answered",
+                    false => user_answer,
+                }),
+                Text::raw(" | ["),
+                Text::styled(keymap.label(Scope::Text, Action::EditAnswer), theme.hint),
+                Text::raw(": type answer]"),
+            ],
+        };
+        frame.render_widget(
+            Paragraph::new(input_display.iter())
+                .block(Block::default().borders(Borders::ALL).title("Your answer")),
+            chunks[1],
+        );
+
+        // Answer
+        if self.display.display_answer {
+            let answer_width = content.width.saturating_sub(2);
+            let mut answer_display = markdown::render_plain(
+                &format!("Accepted: {}", expected),
+                answer_width,
+                theme.correct,
+            );
+            if let Some(explanation) = explanation {
+                answer_display.push(Text::raw("\n"));
+                answer_display.extend(markdown::highlight(
+                    markdown::render(explanation, answer_width, &theme.syntax),
+                    self.display.search_query(),
+                    theme.search_match,
+                ));
+            }
+            frame.render_widget(
+                Paragraph::new(answer_display.iter())
+                    .block(Block::default().borders(Borders::ALL).title("Answer")),
+                chunks[2],
+            );
+        }
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let exam = state.exam.as_ref().unwrap();
+
+        // Typing the answer. Every key is taken as input until submitted.
+        if exam.display.answer_input.is_some() {
+            let is_numeric = matches!(
+                exam.question_at(exam.display.question_index),
+                Some(Item::Numeric(_))
+            );
+            let keyevent = match event {
+                Messages::Input(keyevent) => keyevent,
+                _ => return None,
+            };
+            let evt = match (
+                state.config.keymap.action(Scope::Input, &keyevent),
+                keyevent,
+            ) {
+                (Some(Action::Submit), _) => Some(UpdateAnswerInputEvent::Submit),
+                (Some(Action::Cancel), _) => Some(UpdateAnswerInputEvent::Cancel),
+                (Some(Action::DeleteChar), _) => Some(UpdateAnswerInputEvent::Pop),
+                // Numbers are limited to the characters they are written with
+                (
+                    _,
+                    KeyEvent {
+                        code: KeyCode::Char(c),
+                        modifiers,
+                    },
+                ) if (modifiers == KeyModifiers::empty() || modifiers == KeyModifiers::SHIFT)
+                    && (!is_numeric || c.is_ascii_digit() || "+-.eE".contains(c)) =>
+                {
+                    Some(UpdateAnswerInputEvent::Push(c))
+                }
+                _ => None,
+            };
+            if let Some(evt) = evt {
+                tx.send(Messages::UpdateAnswerInput(evt)).unwrap();
+            }
+            return None;
+        }
+
+        match event {
+            Messages::Input(keyevent) => match state
+                .config
+                .keymap
+                .action_in(&[Scope::Exam, Scope::Item, Scope::Text], &keyevent)
+            {
+                Some(Action::EditAnswer) => {
+                    tx.send(Messages::UpdateAnswerInput(UpdateAnswerInputEvent::Open))
+                        .unwrap();
+                    None
+                }
+                Some(Action::ToggleAnswer) => {
+                    tx.send(Messages::ToggleExamResult).unwrap();
+                    None
+                }
+                Some(Action::ToggleFlag) => {
+                    tx.send(Messages::ToggleFlag).unwrap();
+                    None
+                }
+                Some(Action::Down) => {
+                    scroll_question(exam, 1, &tx);
+                    None
+                }
+                Some(Action::Up) => {
+                    scroll_question(exam, -1, &tx);
+                    None
+                }
+                _ => Some(event),
+            },
+            _ => Some(event),
+        }
+    }
+}
+
+pub struct TrueFalseWidget<'a> {
+    app: &'a App,
+    question: &'a TrueFalseQuestion,
+    display: &'a DoExamDisplay,
+}
+
+impl<'a> TrueFalseWidget<'a> {
+    pub fn new(app: &'a App, question: &'a TrueFalseQuestion, display: &'a DoExamDisplay) -> Self {
+        TrueFalseWidget {
+            app,
+            question,
+            display,
+        }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        const KIND: &str = "True or false";
+        let exam = &self.app.exam.as_ref().unwrap();
+        let item_title = item_title(KIND, exam, self.display);
+
+        let num_assets = self.question.assets.len();
+        let question_display = question_texts(
+            &self.question.question,
+            num_assets,
+            content.width,
+            theme,
+            &self.app.config.keymap,
+            self.display.search_query(),
+        );
+
+        // Question + Choices, or Question + Choices + Answer
+        let chunks = match self.display.display_answer {
+            false => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(10), Constraint::Length(2)].as_ref())
+                .split(content),
+            true => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(10),
+                        Constraint::Length(2),
+                        Constraint::Min(5),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+        };
+
+        // Question
+        frame.render_widget(
+            Paragraph::new(question_display.iter())
+                .block(Block::default().borders(Borders::ALL).title(&item_title))
+                .scroll(self.display.question_scroll_pos),
+            chunks[0],
+        );
+        self.app.mouse_areas.add(chunks[0], MouseTarget::Question);
+
+        // Choices
+        let choice_style =
+            |value: bool| match (self.display.display_answer, self.question.user_answer) {
+                (true, _) if value == self.question.value => theme.correct,
+                (true, Some(user_answer)) if user_answer == value => theme.wrong,
+                (false, Some(user_answer)) if user_answer == value => theme.selected,
+                _ => Style::default(),
+            };
+        let keymap = &self.app.config.keymap;
+        let choices_display = [
+            Text::raw("["),
+            Text::styled(
+                keymap.label(Scope::TrueFalse, Action::AnswerTrue),
+                theme.hint,
+            ),
+            Text::raw("] "),
+            Text::styled(" True ", choice_style(true)),
+            Text::raw("    ["),
+            Text::styled(
+                keymap.label(Scope::TrueFalse, Action::AnswerFalse),
+                theme.hint,
+            ),
+            Text::raw("] "),
+            Text::styled(" False ", choice_style(false)),
+        ];
+        frame.render_widget(
+            Paragraph::new(choices_display.iter())
+                .block(Block::default().borders(Borders::TOP))
+                .alignment(Alignment::Center),
+            chunks[1],
+        );
 
-                match self.question.answer.is_some() {
-                    true => {
-                        // Question + Selection
-                        frame.render_widget(question_block, three_chunks[0]);
-                        frame.render_widget(selections_block, three_chunks[1]);
-                        frame.render_widget(answer_block, three_chunks[2]);
-                    }
-                    false => {
-                        // Question + Selection + Answer
-                        frame.render_widget(question_block, two_chunks[0]);
-                        frame.render_widget(selections_block, two_chunks[1]);
-                    }
-                }
+        // Answer
+        if self.display.display_answer {
+            let mut answer_display = vec![Text::styled(
+                match self.question.value {
+                    true => "The statement is true",
+                    false => "The statement is false",
+                },
+                theme.correct,
+            )];
+            if let Some(explanation) = &self.question.answer {
+                answer_display.push(Text::raw("\n\n"));
+                answer_display.extend(markdown::highlight(
+                    markdown::render(explanation, content.width.saturating_sub(2), &theme.syntax),
+                    self.display.search_query(),
+                    theme.search_match,
+                ));
             }
+            frame.render_widget(
+                Paragraph::new(answer_display.iter())
+                    .block(Block::default().borders(Borders::ALL).title("Answer")),
+                chunks[2],
+            );
         }
     }
 
     pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let exam = state.exam.as_ref().unwrap();
         match event {
-            Messages::Input(keyevent) => match keyevent {
-                key!('a') | key!('A') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::A))
-                        .unwrap();
-                    None
-                }
-                key!('b') | key!('B') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::B))
-                        .unwrap();
-                    None
-                }
-                key!('c') | key!('C') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::C))
-                        .unwrap();
-                    None
-                }
-                key!('d') | key!('D') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::D))
-                        .unwrap();
-                    None
-                }
-                key!('e') | key!('E') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::E))
-                        .unwrap();
+            Messages::Input(keyevent) => match state
+                .config
+                .keymap
+                .action_in(&[Scope::Exam, Scope::Item, Scope::TrueFalse], &keyevent)
+            {
+                Some(Action::AnswerTrue) => {
+                    tx.send(Messages::SetTrueFalse(true)).unwrap();
                     None
                 }
-                key!('f') | key!('F') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::F))
-                        .unwrap();
+                Some(Action::AnswerFalse) => {
+                    tx.send(Messages::SetTrueFalse(false)).unwrap();
                     None
                 }
-                key!('g') | key!('G') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::G))
-                        .unwrap();
+                Some(Action::ToggleFlag) => {
+                    tx.send(Messages::ToggleFlag).unwrap();
                     None
                 }
-                key!('h') | key!('H') => {
-                    tx.send(Messages::ToggleSelection(SelectionFlags::H))
-                        .unwrap();
+                Some(Action::ToggleAnswer) => {
+                    tx.send(Messages::ToggleExamResult).unwrap();
                     None
                 }
-                key!('j') => {
-                    state.exam.as_ref().map(|exam: &Exam| {
-                        tx.send(Messages::ScrollQuestion(
-                            &exam.display.question_scroll_pos + 1,
-                        ))
-                        .unwrap();
-                    });
+                Some(Action::Down) => {
+                    scroll_question(exam, 1, &tx);
                     None
                 }
-                key!('k') => {
-                    state.exam.as_ref().map(|exam: &Exam| {
-                        let next_pos = match &exam.display.question_scroll_pos {
-                            0 => 0,
-                            _ => &exam.display.question_scroll_pos - 1,
-                        };
-                        tx.send(Messages::ScrollQuestion(next_pos)).unwrap();
-                    });
+                Some(Action::Up) => {
+                    scroll_question(exam, -1, &tx);
                     None
                 }
-                key!(' ') => {
-                    tx.send(Messages::ToggleExamResult).unwrap();
-                    return None;
-                }
                 _ => Some(event),
             },
             _ => Some(event),
@@ -1014,6 +2873,433 @@ impl<'a> QuestionWidget<'a> {
     }
 }
 
+pub struct OrderingWidget<'a> {
+    app: &'a App,
+    question: &'a OrderingQuestion,
+    display: &'a DoExamDisplay,
+}
+
+impl<'a> OrderingWidget<'a> {
+    pub fn new(app: &'a App, question: &'a OrderingQuestion, display: &'a DoExamDisplay) -> Self {
+        OrderingWidget {
+            app,
+            question,
+            display,
+        }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        const KIND: &str = "Ordering";
+        let exam = &self.app.exam.as_ref().unwrap();
+        let item_title = item_title(KIND, exam, self.display);
+
+        let num_assets = self.question.assets.len();
+        let question_display = question_texts(
+            &self.question.question,
+            num_assets,
+            content.width,
+            theme,
+            &self.app.config.keymap,
+            self.display.search_query(),
+        );
+
+        let order = exam.step_order(self.display.question_index);
+        let cursor = self.display.entry_cursor.min(order.len().saturating_sub(1));
+        let steps_height = order.len() as u16 + 2;
+
+        // Question + Steps + Status, or Question + Steps + Answer + Status
+        let chunks = match self.display.display_answer {
+            false => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(5),
+                        Constraint::Length(steps_height),
+                        Constraint::Length(2),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+            true => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(5),
+                        Constraint::Length(steps_height),
+                        Constraint::Length(steps_height),
+                        Constraint::Length(2),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+        };
+
+        // Question
+        frame.render_widget(
+            Paragraph::new(question_display.iter())
+                .block(Block::default().borders(Borders::ALL).title(&item_title))
+                .scroll(self.display.question_scroll_pos),
+            chunks[0],
+        );
+        self.app.mouse_areas.add(chunks[0], MouseTarget::Question);
+
+        // Steps, marked right or wrong along with the answer
+        let steps_display: Vec<Text> = order
+            .iter()
+            .enumerate()
+            .map(|(pos, step)| {
+                let text = self
+                    .question
+                    .steps
+                    .get(*step)
+                    .map(|text| text.as_str())
+                    .unwrap_or("");
+                let style = match (self.display.display_answer, pos == cursor) {
+                    (true, _) if pos == *step => theme.correct,
+                    (true, _) => theme.wrong,
+                    (false, true) => theme.cursor,
+                    (false, false) => Style::default(),
+                };
+                Text::styled(format!("{:2}. {}\n", pos + 1, text), style)
+            })
+            .collect();
+        let steps_display = markdown::highlight(
+            steps_display,
+            self.display.search_query(),
+            theme.search_match,
+        );
+        let steps_title = match self.question.is_answered() {
+            true => "Steps",
+            false => "Steps (not arranged)",
+        };
+        frame.render_widget(
+            Paragraph::new(steps_display.iter())
+                .block(Block::default().borders(Borders::ALL).title(steps_title)),
+            chunks[1],
+        );
+
+        // Answer
+        if self.display.display_answer {
+            let mut answer_display: Vec<Text> = self
+                .question
+                .steps
+                .iter()
+                .enumerate()
+                .map(|(pos, text)| Text::raw(format!("{:2}. {}\n", pos + 1, text)))
+                .collect();
+            if let Some(explanation) = &self.question.answer {
+                answer_display.push(Text::raw("\n"));
+                answer_display.extend(markdown::highlight(
+                    markdown::render(explanation, content.width.saturating_sub(2), &theme.syntax),
+                    self.display.search_query(),
+                    theme.search_match,
+                ));
+            }
+            frame.render_widget(
+                Paragraph::new(answer_display.iter())
+                    .block(Block::default().borders(Borders::ALL).title("Answer")),
+                chunks[2],
+            );
+        }
+
+        // Status
+        let keymap = &self.app.config.keymap;
+        let label = |action| keymap.label(Scope::Ordering, action);
+        let status_display = [
+            Text::raw("["),
+            Text::styled(
+                format!("{},{}", label(Action::Down), label(Action::Up)),
+                theme.hint,
+            ),
+            Text::raw(": select] ["),
+            Text::styled(
+                format!("{},{}", label(Action::MoveDown), label(Action::MoveUp)),
+                theme.hint,
+            ),
+            Text::raw(": move] ["),
+            Text::styled(label(Action::Submit), theme.hint),
+            Text::raw(": submit as is]"),
+        ];
+        frame.render_widget(
+            Paragraph::new(status_display.iter())
+                .block(Block::default().borders(Borders::TOP))
+                .alignment(Alignment::Center),
+            chunks[chunks.len() - 1],
+        );
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let action = match &event {
+            Messages::Input(keyevent) => state
+                .config
+                .keymap
+                .action_in(&[Scope::Exam, Scope::Ordering], keyevent),
+            _ => None,
+        };
+        let evt = match action {
+            Some(Action::Down) => UpdateStepOrderEvent::Next,
+            Some(Action::Up) => UpdateStepOrderEvent::Prev,
+            Some(Action::MoveDown) => UpdateStepOrderEvent::MoveDown,
+            Some(Action::MoveUp) => UpdateStepOrderEvent::MoveUp,
+            Some(Action::Submit) => UpdateStepOrderEvent::Submit,
+            Some(Action::ToggleFlag) => {
+                tx.send(Messages::ToggleFlag).unwrap();
+                return None;
+            }
+            Some(Action::ToggleAnswer) => {
+                tx.send(Messages::ToggleExamResult).unwrap();
+                return None;
+            }
+            _ => return Some(event),
+        };
+        tx.send(Messages::UpdateStepOrder(evt)).unwrap();
+        None
+    }
+}
+
+pub struct MatchingWidget<'a> {
+    app: &'a App,
+    question: &'a MatchingQuestion,
+    display: &'a DoExamDisplay,
+}
+
+impl<'a> MatchingWidget<'a> {
+    pub fn new(app: &'a App, question: &'a MatchingQuestion, display: &'a DoExamDisplay) -> Self {
+        MatchingWidget {
+            app,
+            question,
+            display,
+        }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        const KIND: &str = "Matching";
+        let exam = &self.app.exam.as_ref().unwrap();
+        let item_title = item_title(KIND, exam, self.display);
+
+        let num_assets = self.question.assets.len();
+        let question_display = question_texts(
+            &self.question.question,
+            num_assets,
+            content.width,
+            theme,
+            &self.app.config.keymap,
+            self.display.search_query(),
+        );
+
+        let order = exam.match_order(self.display.question_index);
+        let num_pairs = order.len();
+        let cursor = self.display.entry_cursor.min(num_pairs.saturating_sub(1));
+        let pairs_height = num_pairs as u16 + 2;
+        // Right entries are labelled by their displayed position
+        let label_of = |right: usize| {
+            order
+                .iter()
+                .position(|i| *i == right)
+                .map(|pos| (b'A' + pos as u8) as char)
+        };
+
+        // Question + Pairs + Status, or Question + Pairs + Answer + Status
+        let chunks = match (self.display.display_answer, &self.question.answer) {
+            (true, Some(_)) => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(5),
+                        Constraint::Length(pairs_height),
+                        Constraint::Min(3),
+                        Constraint::Length(2),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+            _ => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Min(5),
+                        Constraint::Length(pairs_height),
+                        Constraint::Length(2),
+                    ]
+                    .as_ref(),
+                )
+                .split(content),
+        };
+        let column_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(chunks[1]);
+
+        // Question
+        frame.render_widget(
+            Paragraph::new(question_display.iter())
+                .block(Block::default().borders(Borders::ALL).title(&item_title))
+                .scroll(self.display.question_scroll_pos),
+            chunks[0],
+        );
+        self.app.mouse_areas.add(chunks[0], MouseTarget::Question);
+
+        // Left entries with their pairs, marked right or wrong along with
+        // the answer
+        let left_display: Vec<Text> = self
+            .question
+            .pairs
+            .iter()
+            .take(num_pairs)
+            .enumerate()
+            .map(|(left, pair)| {
+                let user_label = self.question.user_match(left).and_then(&label_of);
+                let text = match (self.display.display_answer, user_label) {
+                    (true, Some(label)) if self.question.user_match(left) != Some(left) => format!(
+                        "{:2}. {} -> {} ({})\n",
+                        left + 1,
+                        pair.left,
+                        label,
+                        label_of(left).unwrap_or('?')
+                    ),
+                    (true, None) => format!(
+                        "{:2}. {} -> _ ({})\n",
+                        left + 1,
+                        pair.left,
+                        label_of(left).unwrap_or('?')
+                    ),
+                    (_, Some(label)) => format!("{:2}. {} -> {}\n", left + 1, pair.left, label),
+                    (false, None) => format!("{:2}. {} -> _\n", left + 1, pair.left),
+                };
+                let style = match (self.display.display_answer, left == cursor) {
+                    (true, _) if self.question.user_match(left) == Some(left) => theme.correct,
+                    (true, _) => theme.wrong,
+                    (false, true) => theme.cursor,
+                    (false, false) => Style::default(),
+                };
+                Text::styled(text, style)
+            })
+            .collect();
+        let left_display = markdown::highlight(
+            left_display,
+            self.display.search_query(),
+            theme.search_match,
+        );
+        frame.render_widget(
+            Paragraph::new(left_display.iter())
+                .block(Block::default().borders(Borders::ALL).title("Match")),
+            column_chunks[0],
+        );
+
+        // Right entries
+        let right_display: Vec<Text> = order
+            .iter()
+            .enumerate()
+            .map(|(pos, right)| {
+                let text = self
+                    .question
+                    .pairs
+                    .get(*right)
+                    .map(|pair| pair.right.as_str())
+                    .unwrap_or("");
+                Text::raw(format!("{}. {}\n", (b'A' + pos as u8) as char, text))
+            })
+            .collect();
+        let right_display = markdown::highlight(
+            right_display,
+            self.display.search_query(),
+            theme.search_match,
+        );
+        frame.render_widget(
+            Paragraph::new(right_display.iter())
+                .block(Block::default().borders(Borders::ALL).title("With")),
+            column_chunks[1],
+        );
+
+        // Answer
+        if let (true, Some(explanation)) = (self.display.display_answer, &self.question.answer) {
+            let answer_display = markdown::highlight(
+                markdown::render(explanation, content.width.saturating_sub(2), &theme.syntax),
+                self.display.search_query(),
+                theme.search_match,
+            );
+            frame.render_widget(
+                Paragraph::new(answer_display.iter())
+                    .block(Block::default().borders(Borders::ALL).title("Answer")),
+                chunks[2],
+            );
+        }
+
+        // Status
+        let keymap = &self.app.config.keymap;
+        let label = |action| keymap.label(Scope::Matching, action);
+        let status_display = [
+            Text::raw("["),
+            Text::styled(
+                format!("{},{}", label(Action::Down), label(Action::Up)),
+                theme.hint,
+            ),
+            Text::raw(": select] ["),
+            Text::styled(
+                label_keys_hint(
+                    keymap,
+                    &[Scope::Global, Scope::Exam, Scope::Matching],
+                    self.question.num_pairs(),
+                ),
+                theme.hint,
+            ),
+            Text::raw(": pair] ["),
+            Text::styled(label(Action::Unpair), theme.hint),
+            Text::raw(": unpair]"),
+        ];
+        frame.render_widget(
+            Paragraph::new(status_display.iter())
+                .block(Block::default().borders(Borders::TOP))
+                .alignment(Alignment::Center),
+            chunks[chunks.len() - 1],
+        );
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let action = match &event {
+            Messages::Input(keyevent) => state
+                .config
+                .keymap
+                .action_in(&[Scope::Exam, Scope::Matching], keyevent),
+            _ => None,
+        };
+        let evt = match (action, &event) {
+            (Some(Action::Down), _) => UpdateMatchingEvent::Next,
+            (Some(Action::Up), _) => UpdateMatchingEvent::Prev,
+            (Some(Action::Unpair), _) => UpdateMatchingEvent::Clear,
+            (Some(Action::ToggleFlag), _) => {
+                tx.send(Messages::ToggleFlag).unwrap();
+                return None;
+            }
+            (Some(Action::ToggleAnswer), _) => {
+                tx.send(Messages::ToggleExamResult).unwrap();
+                return None;
+            }
+            // Right entries are paired by their labels. As with selections,
+            // labels bound to other keys can be typed in upper case or with Alt.
+            (
+                None,
+                Messages::Input(KeyEvent {
+                    code: KeyCode::Char(c),
+                    modifiers,
+                }),
+            ) if *modifiers == KeyModifiers::empty() || *modifiers == KeyModifiers::ALT => {
+                let exam = state.exam.as_ref()?;
+                let order = exam.match_order(exam.display.question_index);
+                match SelectionFlags::index_of_label(*c).and_then(|pos| order.get(pos)) {
+                    Some(right) => UpdateMatchingEvent::Assign(*right),
+                    None => return Some(event),
+                }
+            }
+            _ => return Some(event),
+        };
+        tx.send(Messages::UpdateMatching(evt)).unwrap();
+        None
+    }
+}
+
 pub struct ExamItemsWidget<'a> {
     app: &'a App,
 }
@@ -1024,10 +3310,16 @@ impl<'a> ExamItemsWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
         let mut texts: Vec<Text> = vec![];
         let exam = self.app.exam.as_ref().unwrap();
-        let num_questions = exam.num_questions();
         let current_index = exam.display.question_index;
+        let visible_indices = exam.visible_indices();
+        let num_questions = visible_indices.len();
+        let current_pos = visible_indices
+            .iter()
+            .position(|index| *index == current_index)
+            .unwrap_or(0);
 
         let selections_height = if num_questions as u16 % self.app.config.items_per_line == 0 {
             num_questions as u16 / self.app.config.items_per_line
@@ -1038,93 +3330,74 @@ impl<'a> ExamItemsWidget<'a> {
             0
         } else {
             let diff = 2 + selections_height - content.height;
-            let mut a = (diff as usize * current_index) as f32;
+            let mut a = (diff as usize * current_pos) as f32;
             a /= num_questions as f32;
             a.round() as u16
         };
 
-        const PENDING_STYLE: Style = Style {
-            fg: Color::Black,
-            bg: Color::Gray,
-            modifier: Modifier::empty(),
-        };
-        const CURRENT_STYLE: Style = Style {
-            fg: Color::Gray,
-            bg: Color::Magenta,
-            modifier: Modifier::BOLD,
-        };
-        const DONE_STYLE: Style = Style {
-            fg: Color::White,
-            bg: Color::Blue,
-            modifier: Modifier::empty(),
-        };
-        const WRONG_STYLE: Style = Style {
-            fg: Color::White,
-            bg: Color::Red,
-            modifier: Modifier::empty(),
-        };
-        const CORRECT_STYLE: Style = Style {
-            fg: Color::White,
-            bg: Color::Green,
-            modifier: Modifier::empty(),
-        };
-
-        let qitems = exam.questions.iter().enumerate();
+        // Only items under review are listed
+        let qitems = visible_indices
+            .iter()
+            .filter_map(|index| Some((*index, exam.question_at(*index)?)))
+            .enumerate();
         let items_per_line = self.app.config.items_per_line;
 
         match exam.display.display_answer {
-            false => qitems.for_each(|(index, item)| {
+            false => qitems.for_each(|(pos, (index, item))| {
                 // Text
                 if index == current_index {
-                    texts.push(Text::styled(format!("{:3}", &index + 1), CURRENT_STYLE));
+                    let mut style = theme.item_current;
+                    if item.is_flagged() {
+                        style.modifier |= Modifier::UNDERLINED;
+                    }
+                    texts.push(Text::styled(
+                        format!("{:3}", exam.display_number(index)),
+                        style,
+                    ));
                 } else {
-                    let style = match item {
-                        Item::Question(question) => match question.get_result() {
-                            QuestionResult::Pending => PENDING_STYLE,
-                            _ => DONE_STYLE,
-                        },
-                        _ => DONE_STYLE,
+                    let style = match (item.is_flagged(), item.get_result(exam.scoring)) {
+                        (true, _) => theme.item_flagged,
+                        (false, QuestionResult::Pending) => theme.item_pending,
+                        (false, _) => theme.item_done,
                     };
-                    texts.push(Text::styled(format!("{:3}", &index + 1), style));
+                    texts.push(Text::styled(
+                        format!("{:3}", exam.display_number(index)),
+                        style,
+                    ));
                 }
 
                 // Separator
-                if (index + 1) as u16 % items_per_line == 0 {
+                if ((pos + 1) as u16).is_multiple_of(items_per_line) {
                     texts.push(Text::raw("\n"));
                 } else {
                     texts.push(Text::raw(" "));
                 }
             }),
-            true => qitems.for_each(|(index, item)| {
+            true => qitems.for_each(|(pos, (index, item))| {
                 // Text
-                match item {
-                    Item::Question(question) => {
-                        let mut style = match question.get_result() {
-                            QuestionResult::Correct => CORRECT_STYLE,
-                            QuestionResult::Wrong => WRONG_STYLE,
-                            QuestionResult::Pending => PENDING_STYLE,
-                            QuestionResult::Done => DONE_STYLE,
-                        };
-                        if current_index == index {
-                            style.modifier = Modifier::BOLD;
-                            style.fg = style.bg;
-                            style.bg = Color::Rgb(209, 162, 226);
-                        };
-                        texts.push(Text::styled(format!("{:3}", &index + 1), style));
-                    }
-                    Item::Card(_) => {
-                        let mut style = PENDING_STYLE;
-                        if current_index == index {
-                            style.modifier = Modifier::BOLD;
-                            style.fg = style.bg;
-                            style.bg = Color::Magenta;
-                        };
-                        texts.push(Text::styled(format!("{:3}", &index + 1), style));
-                    }
+                let mut style = match item.get_result(exam.scoring) {
+                    QuestionResult::Correct => theme.item_correct,
+                    QuestionResult::Partial => theme.item_partial,
+                    QuestionResult::Wrong => theme.item_wrong,
+                    QuestionResult::Pending => theme.item_pending,
+                    QuestionResult::Done => theme.item_done,
+                };
+                if current_index == index {
+                    style.modifier |= theme.item_reviewed.modifier;
+                    style.fg = style.bg;
+                    style.bg = theme.item_reviewed.bg;
                 };
+                // Results are kept visible, so flags are only underlined
+                if item.is_flagged() {
+                    style.modifier |= Modifier::UNDERLINED;
+                }
+                texts.push(Text::styled(
+                    format!("{:3}", exam.display_number(index)),
+                    style,
+                ));
 
                 // Separator
-                if (index + 1) as u16 % items_per_line == 0 {
+                if ((pos + 1) as u16).is_multiple_of(items_per_line) {
                     texts.push(Text::raw("\n"));
                 } else {
                     texts.push(Text::raw(" "));
@@ -1132,33 +3405,71 @@ impl<'a> ExamItemsWidget<'a> {
             }),
         }
 
+        let sidebar_title = match (&exam.display.review_items, exam.display.studying) {
+            (Some(_), true) => "Due",
+            (Some(_), false) => "Review",
+            (None, _) => "Items",
+        };
         let sidebar_display = Paragraph::new(texts.iter())
-            .block(Block::default().borders(Borders::ALL).title("Items"))
+            .block(Block::default().borders(Borders::ALL).title(sidebar_title))
             .scroll(scroll_pos);
         frame.render_widget(sidebar_display, content);
+
+        // Numbers are clicked to jump to their items
+        let inner = Block::default().borders(Borders::ALL).inner(content);
+        visible_indices.iter().enumerate().for_each(|(pos, index)| {
+            let row = pos as u16 / items_per_line;
+            let column = pos as u16 % items_per_line;
+            if row < scroll_pos || row - scroll_pos >= inner.height || column * 4 + 3 > inner.width
+            {
+                return;
+            }
+            let area = Rect::new(inner.x + column * 4, inner.y + row - scroll_pos, 3, 1);
+            self.app.mouse_areas.add(area, MouseTarget::Item(*index));
+        });
     }
 
-    pub fn propagate(
-        _state: &App,
-        event: Messages,
-        tx: mpsc::Sender<Messages>,
-    ) -> Option<Messages> {
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
         match event {
-            Messages::Input(keyevent) => match keyevent {
-                key!('>') | key!('n') => {
+            Messages::Input(keyevent) => match state.config.keymap.action(Scope::Exam, &keyevent) {
+                Some(Action::NextItem) => {
                     tx.send(Messages::UpdateQuestionIndex(
                         UpdateQuestionIndexEvent::Next,
                     ))
                     .unwrap();
                     None
                 }
-                key!('<') | key!('p') => {
+                Some(Action::PrevItem) => {
                     tx.send(Messages::UpdateQuestionIndex(
                         UpdateQuestionIndexEvent::Prev,
                     ))
                     .unwrap();
                     None
                 }
+                Some(Action::NextFlagged) => {
+                    tx.send(Messages::UpdateQuestionIndex(
+                        UpdateQuestionIndexEvent::NextFlagged,
+                    ))
+                    .unwrap();
+                    None
+                }
+                Some(Action::PrevFlagged) => {
+                    tx.send(Messages::UpdateQuestionIndex(
+                        UpdateQuestionIndexEvent::PrevFlagged,
+                    ))
+                    .unwrap();
+                    None
+                }
+                _ => Some(event),
+            },
+            Messages::Mouse(mouseevent) => match state.mouse_areas.action(&mouseevent) {
+                Some(MouseAction::Click(MouseTarget::Item(index))) => {
+                    tx.send(Messages::UpdateQuestionIndex(
+                        UpdateQuestionIndexEvent::Set(index),
+                    ))
+                    .unwrap();
+                    None
+                }
                 _ => Some(event),
             },
             _ => Some(event),
@@ -1176,20 +3487,16 @@ impl<'a> JumpBarWidget<'a> {
     }
 
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
-        const JUMPBOX_TEXT_STYLE: Style = Style {
-            fg: Color::Black,
-            bg: Color::Reset,
-            modifier: Modifier::UNDERLINED,
-        };
+        let theme = &self.app.config.theme;
 
         let sidebar_inner_length = self.app.config.items_per_line * 4 - 1;
         let jumpbox_value = self.app.exam.as_ref().unwrap().jumpbox_value;
         let jumpbox_text = [
             Text::styled(
                 " ".repeat(sidebar_inner_length as usize - jumpbox_value.to_string().len()),
-                JUMPBOX_TEXT_STYLE,
+                theme.jumpbox,
             ),
-            Text::styled(format!("{}", jumpbox_value), JUMPBOX_TEXT_STYLE),
+            Text::styled(format!("{}", jumpbox_value), theme.jumpbox),
         ];
 
         frame.render_widget(
@@ -1203,11 +3510,20 @@ impl<'a> JumpBarWidget<'a> {
         let exam = state.exam.as_ref().unwrap();
         let jumpbox_value = exam.jumpbox_value;
         match event {
-            Messages::Input(keyevent) => match keyevent {
-                key!(Char(c)) => {
-                    if c < '0' || c > '9' {
-                        return Some(event);
-                    };
+            Messages::Input(keyevent) => match (
+                state.config.keymap.action(Scope::Input, &keyevent),
+                keyevent,
+            ) {
+                // Digits bound to actions of the item are left to them
+                (_, key!(Char(c)))
+                    if c.is_ascii_digit()
+                        && exam
+                            .question_at(exam.display.question_index)
+                            .and_then(|item| {
+                                state.config.keymap.action_in(item_scopes(item), &keyevent)
+                            })
+                            .is_none() =>
+                {
                     let next_value = jumpbox_value * 10 + c.to_digit(10)? as u16;
                     if next_value > exam.num_questions() as u16 {
                         tx.send(Messages::UpdateJumpboxValue(exam.num_questions() as u16))
@@ -1217,20 +3533,21 @@ impl<'a> JumpBarWidget<'a> {
                     }
                     None
                 }
-                key!(Enter) => {
-                    // Do not handle Enter if Jumpbox is closed
+                (Some(Action::Submit), _) => {
+                    // Do not submit if Jumpbox is closed
                     if jumpbox_value == 0 {
                         return Some(event);
                     }
 
+                    let index = exam.index_at_number(jumpbox_value as usize)?;
                     tx.send(Messages::UpdateQuestionIndex(
-                        UpdateQuestionIndexEvent::Set(jumpbox_value as usize - 1),
+                        UpdateQuestionIndexEvent::Set(index),
                     ))
                     .unwrap();
                     tx.send(Messages::UpdateJumpboxValue(0)).unwrap();
                     None
                 }
-                key!(Backspace) => {
+                (Some(Action::DeleteChar), _) => {
                     if jumpbox_value != 0 {
                         tx.send(Messages::UpdateJumpboxValue(jumpbox_value / 10))
                             .unwrap();
@@ -1239,7 +3556,7 @@ impl<'a> JumpBarWidget<'a> {
                         Some(event)
                     }
                 }
-                key!(Esc) => {
+                (Some(Action::Cancel), _) => {
                     if jumpbox_value != 0 {
                         tx.send(Messages::UpdateJumpboxValue(0)).unwrap();
                         None
@@ -1253,3 +3570,201 @@ impl<'a> JumpBarWidget<'a> {
         }
     }
 }
+
+pub struct ExamSearchWidget<'a> {
+    app: &'a App,
+}
+
+impl<'a> ExamSearchWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        ExamSearchWidget { app }
+    }
+
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, content: Rect) {
+        let theme = &self.app.config.theme;
+        let exam = self.app.exam.as_ref().unwrap();
+
+        // Search input
+        let content = match &exam.display.search_input {
+            Some(input) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(3), Constraint::Min(5)].as_ref())
+                    .split(content);
+                let input_texts = [Text::styled(format!("{}_", input), theme.input)];
+                frame.render_widget(
+                    Paragraph::new(input_texts.iter())
+                        .block(Block::default().borders(Borders::ALL).title("Search")),
+                    chunks[0],
+                );
+                chunks[1]
+            }
+            None => content,
+        };
+
+        // Items are listed until a search is submitted
+        let search = match &exam.display.search {
+            Some(search) => search,
+            None => return ExamItemsWidget::new(self.app).draw(frame, content),
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)].as_ref())
+            .split(content);
+
+        // Results, by the first line of their question
+        let title = match search.results.len() {
+            0 => "No matches".to_owned(),
+            num_results => format!("Matches ({}/{})", search.current + 1, num_results),
+        };
+        let list_items = search.results.iter().map(|index| {
+            let first_line = exam
+                .question_at(*index)
+                .and_then(|item| item.get_text().lines().find(|line| !line.trim().is_empty()))
+                .unwrap_or("");
+            Text::raw(format!(
+                "{:3} {}",
+                exam.display_number(*index),
+                first_line.trim()
+            ))
+        });
+        let list_block = Block::default().borders(Borders::ALL).title(&title);
+        let list_area = list_block.inner(chunks[0]);
+        let list_widget = List::new(list_items)
+            .block(list_block)
+            .highlight_symbol(">")
+            .highlight_style(Style::default().modifier(Modifier::REVERSED));
+        let mut list_state = ListState::default();
+        if !search.results.is_empty() {
+            list_state.select(Some(search.current));
+        }
+        frame.render_stateful_widget(list_widget, chunks[0], &mut list_state);
+
+        // Rows are clicked to jump to their results. The list scrolls just
+        // enough to show the current result.
+        let offset = (search.current + 1).saturating_sub(list_area.height as usize);
+        (offset..search.results.len())
+            .take(list_area.height as usize)
+            .enumerate()
+            .for_each(|(row, pos)| {
+                let area = Rect::new(list_area.x, list_area.y + row as u16, list_area.width, 1);
+                self.app
+                    .mouse_areas
+                    .add(area, MouseTarget::SearchResult(pos));
+            });
+
+        // Usage
+        let keymap = &self.app.config.keymap;
+        let label = |action| keymap.label(Scope::Search, action);
+        let usage_texts = [Text::styled(
+            format!(
+                "[{}/{}: next/prev][{}: close]",
+                label(Action::NextMatch),
+                label(Action::PrevMatch),
+                label(Action::Close)
+            ),
+            theme.hint,
+        )];
+        frame.render_widget(Paragraph::new(usage_texts.iter()), chunks[1]);
+    }
+
+    pub fn propagate(state: &App, event: Messages, tx: mpsc::Sender<Messages>) -> Option<Messages> {
+        let display = &state.exam.as_ref()?.display;
+        let keymap = &state.config.keymap;
+        let action = match (&event, &display.search_input) {
+            (Messages::Input(keyevent), Some(_)) => {
+                match (keymap.action(Scope::Input, keyevent), keyevent) {
+                    (Some(Action::Submit), _) => {
+                        Some(ExamSearchActions::Search(UpdateSearchEvent::Submit))
+                    }
+                    (Some(Action::Cancel), _) => {
+                        Some(ExamSearchActions::Search(UpdateSearchEvent::Cancel))
+                    }
+                    (Some(Action::DeleteChar), _) => {
+                        Some(ExamSearchActions::Search(UpdateSearchEvent::Pop))
+                    }
+                    (
+                        _,
+                        KeyEvent {
+                            code: KeyCode::Char(c),
+                            modifiers,
+                        },
+                    ) if *modifiers == KeyModifiers::empty()
+                        || *modifiers == KeyModifiers::SHIFT =>
+                    {
+                        Some(ExamSearchActions::Search(UpdateSearchEvent::Push(*c)))
+                    }
+                    _ => None,
+                }
+            }
+            (Messages::Input(keyevent), None) => match keymap.action(Scope::Search, keyevent) {
+                Some(Action::NextMatch) => Some(ExamSearchActions::NextMatch),
+                Some(Action::PrevMatch) => Some(ExamSearchActions::PrevMatch),
+                Some(Action::Search) => Some(ExamSearchActions::Search(UpdateSearchEvent::Open)),
+                Some(Action::Close) => Some(ExamSearchActions::Close),
+                _ => None,
+            },
+            (Messages::Mouse(mouseevent), _) => match state.mouse_areas.action(mouseevent) {
+                Some(MouseAction::Click(MouseTarget::SearchResult(pos))) => {
+                    Some(ExamSearchActions::Select(pos))
+                }
+                Some(MouseAction::ScrollDown(MouseTarget::SearchResult(_))) => {
+                    Some(ExamSearchActions::NextMatch)
+                }
+                Some(MouseAction::ScrollUp(MouseTarget::SearchResult(_))) => {
+                    Some(ExamSearchActions::PrevMatch)
+                }
+                // Other clicks reach the item
+                _ => return Some(event),
+            },
+            _ => None,
+        };
+        if let Some(action) = action {
+            tx.send(Messages::ExamSearch(action)).unwrap();
+        }
+        None // Blocks all other inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(before: &str, hotkey: &str, after: &str) -> (String, String, String) {
+        (before.to_owned(), hotkey.to_owned(), after.to_owned())
+    }
+
+    #[test]
+    fn hotkeys_underline_a_letter_of_the_name() {
+        assert_eq!(hotkey_parts("CLOSE", "c"), parts("", "C", "LOSE"));
+        assert_eq!(hotkey_parts("PREVIEW", "v"), parts("PRE", "V", "IEW"));
+        assert_eq!(hotkey_parts("SAVE AS", "a"), parts("SAVE ", "A", "S"));
+        assert_eq!(hotkey_parts("Assets", "t"), parts("Asse", "t", "s"));
+    }
+
+    #[test]
+    fn other_hotkeys_are_put_in_front() {
+        assert_eq!(hotkey_parts("CLOSE", "x"), parts("", "x", " CLOSE"));
+        assert_eq!(hotkey_parts("CLOSE", "Esc"), parts("", "Esc", " CLOSE"));
+        assert_eq!(hotkey_parts("OK", "^o"), parts("", "^o", " OK"));
+    }
+
+    #[test]
+    fn buttons_fill_their_width() {
+        let theme = Theme::default();
+        let text = |name, key| {
+            btn_texts(name, key, &theme)
+                .into_iter()
+                .map(|text| match text {
+                    Text::Raw(text) | Text::Styled(text, _) => text.into_owned(),
+                })
+                .collect::<String>()
+        };
+        assert_eq!(text("OK", "o"), "   OK   ");
+        assert_eq!(text("CANCEL", "c"), " CANCEL ");
+        assert_eq!(text("PREVIEW", "p"), " PREVIEW");
+        assert_eq!(text("CLOSE", "Esc"), "Esc CLOS");
+        assert_eq!(text("OK", "^Alt-Enter"), "^Alt-Ent");
+    }
+}